@@ -0,0 +1,152 @@
+//! Just enough of the Atom feeds returned by the arXiv API.
+
+use chrono::{DateTime, Utc};
+use xml::reader::{EventReader, XmlEvent};
+
+use super::{ErrorKind, Result};
+
+const ATOM: &'static str = "http://www.w3.org/2005/Atom";
+const ARXIV: &'static str = "http://arxiv.org/schemas/atom";
+
+/// A paper, from an entry of a feed.
+#[derive(Clone, Debug, Default)]
+pub struct Paper {
+    id: String,
+    title: String,
+    summary: String,
+    published: String,
+    authors: Vec<String>,
+    primary_category: Option<String>,
+}
+
+impl Paper {
+    /// The abstract page of the paper, including its version.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The abstract, as plain text.
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    /// When the first version was submitted.
+    pub fn published(&self) -> Option<DateTime<Utc>> {
+        self.published.parse().ok()
+    }
+
+    pub fn authors(&self) -> &[String] {
+        &self.authors
+    }
+
+    /// The main subject class, e.g. `cs.CL`.
+    pub fn primary_category(&self) -> Option<&str> {
+        self.primary_category.as_ref().map(String::as_str)
+    }
+
+    /// Whether this entry is the API reporting an error, rather than a paper.
+    fn is_error(&self) -> bool {
+        self.id.contains("/api/errors")
+    }
+}
+
+/// Parse the first entry of a feed, or `None` if it has none.
+///
+/// Errors the API reports as entries are returned as `ErrorKind::Api`.
+pub fn parse(xml: &str) -> Result<Option<Paper>> {
+    let mut path: Vec<(String, String)> = vec![];
+    let mut paper: Option<Paper> = None;
+
+    for event in EventReader::from_str(xml) {
+        match event? {
+            XmlEvent::StartElement { name, attributes, .. } => {
+                let namespace = name.namespace.unwrap_or_default();
+
+                if namespace == ATOM && name.local_name == "entry" && paper.is_none() {
+                    paper = Some(Paper::default());
+                }
+
+                if namespace == ARXIV && name.local_name == "primary_category" {
+                    if let Some(ref mut paper) = paper {
+                        paper.primary_category = attributes
+                            .into_iter()
+                            .find(|attribute| attribute.name.local_name == "term")
+                            .map(|attribute| attribute.value);
+                    }
+                }
+
+                path.push((namespace, name.local_name));
+            }
+            XmlEvent::EndElement { .. } => {
+                let ended = path.pop();
+
+                // Only the first entry is of interest.
+                if ended.map_or(false, |(ref n, ref l)| n == ATOM && l == "entry") {
+                    break;
+                }
+            }
+            XmlEvent::Characters(text) | XmlEvent::CData(text) => {
+                let paper = match paper {
+                    Some(ref mut paper) => paper,
+                    None => continue,
+                };
+
+                let names: Vec<&str> = path.iter()
+                    .filter(|&&(ref namespace, _)| namespace == ATOM)
+                    .map(|&(_, ref local_name)| local_name.as_str())
+                    .collect();
+
+                match names.join("/").as_str() {
+                    "feed/entry/id" => paper.id.push_str(&text),
+                    "feed/entry/title" => paper.title.push_str(&text),
+                    "feed/entry/summary" => paper.summary.push_str(&text),
+                    "feed/entry/published" => paper.published.push_str(&text),
+                    "feed/entry/author/name" => paper.authors.push(text),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    match paper {
+        Some(ref paper) if paper.is_error() => {
+            bail!(ErrorKind::Api(collapse_whitespace(&paper.summary)))
+        }
+        Some(mut paper) => {
+            paper.title = collapse_whitespace(&paper.title);
+            paper.summary = collapse_whitespace(&paper.summary);
+            paper.published = paper.published.trim().to_string();
+            Ok(Some(paper))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Titles and abstracts are hard-wrapped, so put them back on one line.
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[test]
+fn test_parse_error() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <entry>
+    <id>http://arxiv.org/api/errors#incorrect_id_format_for_1234</id>
+    <title>Error</title>
+    <summary>incorrect id format for 1234</summary>
+  </entry>
+</feed>"#;
+
+    match parse(xml) {
+        Err(super::Error(ErrorKind::Api(message), _)) => {
+            assert_eq!("incorrect id format for 1234", message)
+        }
+        result => panic!("Expected an API error, got {:?}", result),
+    }
+}
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use url::percent_encoding::{utf8_percent_encode, QUERY_ENCODE_SET};
+
+use util::http::{self, Limits};
+
+use super::{ErrorKind, Result};
+
+use super::atom::{self, Paper};
+
+pub const API_BASE_URL: &'static str = "http://export.arxiv.org/api";
+
+/// Talks to the arXiv API, which returns Atom feeds.
+pub struct Client {
+    base_url: String,
+    limits: Limits,
+}
+
+impl Client {
+    pub fn new() -> Client {
+        Client::with_base_url(API_BASE_URL)
+    }
+
+    /// Create a client which talks to an API rooted at `base_url` instead of
+    /// arXiv's, e.g. `http://localhost:8080/api`.
+    pub fn with_base_url<S: Into<String>>(base_url: S) -> Client {
+        Client {
+            base_url: base_url.into().trim_right_matches('/').to_string(),
+            // The API is slow to respond.
+            limits: Limits {
+                timeout: Duration::from_secs(10),
+                ..Limits::default()
+            },
+        }
+    }
+
+    pub fn get_paper(&self, id: &str) -> Result<Paper> {
+        let url = format!(
+            "{}/query?id_list={}",
+            self.base_url,
+            utf8_percent_encode(id, QUERY_ENCODE_SET)
+        );
+
+        let page = http::get(&url, self.limits)?;
+
+        match atom::parse(page.body())? {
+            Some(paper) => Ok(paper),
+            None => bail!(ErrorKind::NotFound(id.to_string())),
+        }
+    }
+}
+
+/// A `Client` talking to a server for the fixtures in `tests/fixtures/arxiv`.
+///
+/// The server ignores the query, so every paper is the one in
+/// `api/query.xml`.
+#[cfg(test)]
+pub fn fixtures() -> Client {
+    use util::fixture_server::FixtureServer;
+
+    Client::with_base_url(FixtureServer::start("arxiv").url("/api"))
+}
@@ -0,0 +1,103 @@
+use url::Url;
+
+/// Whether `text` is between `min` and `max` digits long.
+fn is_digits(text: &str, min: usize, max: usize) -> bool {
+    text.len() >= min && text.len() <= max && text.chars().all(|c| c.is_digit(10))
+}
+
+/// Whether something looks like an arXiv identifier, optionally versioned.
+///
+/// These are either new-style, e.g. `1706.03762v5`, with four digits for the
+/// year and month and then four or five for the paper, or old-style, e.g.
+/// `hep-th/9901001` or `math.GT/0309136`, with seven digits after the
+/// archive.
+fn is_id(id: &str) -> bool {
+    let (archive, number) = match id.find('/') {
+        Some(index) => (Some(&id[..index]), &id[index + 1..]),
+        None => (None, id),
+    };
+
+    let number = match number.find('v') {
+        Some(index) => {
+            if !is_digits(&number[index + 1..], 1, usize::max_value()) {
+                return false;
+            }
+
+            &number[..index]
+        }
+        None => number,
+    };
+
+    match archive {
+        Some(archive) => {
+            !archive.is_empty() &&
+                archive.chars().all(|c| c.is_alphabetic() || c == '-' || c == '.') &&
+                is_digits(number, 7, 7)
+        }
+        None => {
+            match number.find('.') {
+                Some(index) => {
+                    is_digits(&number[..index], 4, 4) && is_digits(&number[index + 1..], 4, 5)
+                }
+                None => false,
+            }
+        }
+    }
+}
+
+/// Figure out which paper a URL refers to, if any, returning its ID.
+///
+/// This understands abstract and PDF links, with or without a version.
+pub fn recognize(url: &Url) -> Option<String> {
+    match url.host_str() {
+        Some("arxiv.org") | Some("www.arxiv.org") | Some("export.arxiv.org") => {}
+        _ => return None,
+    }
+
+    let path = url.path();
+
+    let id = if path.starts_with("/abs/") {
+        &path["/abs/".len()..]
+    } else if path.starts_with("/pdf/") {
+        path["/pdf/".len()..].trim_right_matches(".pdf")
+    } else {
+        return None;
+    };
+
+    let id = id.trim_right_matches('/');
+
+    if is_id(id) { Some(id.to_string()) } else { None }
+}
+
+#[test]
+fn test_recognize() {
+    let cases = vec![
+        ("https://arxiv.org/abs/1706.03762", Some("1706.03762")),
+        ("https://arxiv.org/abs/1706.03762v5", Some("1706.03762v5")),
+        ("https://arxiv.org/pdf/1706.03762.pdf", Some("1706.03762")),
+        ("https://arxiv.org/pdf/1706.03762v2", Some("1706.03762v2")),
+        ("http://export.arxiv.org/abs/hep-th/9901001", Some("hep-th/9901001")),
+        ("https://arxiv.org/abs/math.GT/0309136v1", Some("math.GT/0309136v1")),
+        ("https://arxiv.org/abs/", None),
+        ("https://arxiv.org/abs/1706.03762v", None),
+        ("https://arxiv.org/abs/not-an-id", None),
+        ("https://arxiv.org/abs/0704.0001", Some("0704.0001")),
+        ("https://arxiv.org/abs/1.2", None),
+        ("https://arxiv.org/abs/1706.03762.1", None),
+        ("https://arxiv.org/abs/17060.3762", None),
+        ("https://arxiv.org/abs/hep-th/99", None),
+        ("https://arxiv.org/list/cs.CL/recent", None),
+        ("https://example.com/abs/1706.03762", None),
+    ];
+
+    for (url, expected) in cases {
+        let parsed = Url::parse(url).expect("Couldn't parse test URL");
+
+        assert_eq!(
+            expected.map(String::from),
+            recognize(&parsed),
+            "recognizing {}",
+            url
+        );
+    }
+}
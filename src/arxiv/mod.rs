@@ -0,0 +1,40 @@
+use xml;
+
+use util::http;
+
+mod client;
+mod atom;
+mod link;
+mod previewer;
+
+pub use self::atom::Paper;
+pub use self::client::Client;
+pub use self::link::recognize;
+pub use self::previewer::Arxiv;
+
+pub const THUMBNAIL: &'static str = "https://arxiv.org/favicon.ico";
+pub const RED: u64 = 0xB31B1B;
+
+error_chain! {
+    links {
+        Http(http::Error, http::ErrorKind);
+    }
+
+    foreign_links {
+        Xml(xml::reader::Error);
+    }
+
+    errors {
+        UnrecognizedUrl {
+            description("URL doesn't refer to an arXiv paper")
+        }
+        NotFound(id: String) {
+            description("The paper wasn't found")
+            display("arXiv paper {} wasn't found", id)
+        }
+        Api(message: String) {
+            description("The arXiv API returned an error")
+            display("The arXiv API returned an error: {}", message)
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use url::Url;
+
+use slog::Logger;
+
+use errors;
+use preview::{Preview, Card};
+use util::html_to_markdown::escape;
+use util::truncate::truncate;
+
+use super::{ErrorKind, Result};
+
+use super::client::Client;
+use super::link;
+
+/// The most characters of an abstract shown in a preview.
+pub const ABSTRACT_LENGTH: usize = 800;
+
+/// List the first `max` authors, summarizing the rest, e.g. "A, B and 3
+/// others".
+fn abbreviate_authors(authors: &[String], max: usize) -> String {
+    let max = if max == 0 { 1 } else { max };
+
+    let (shown, others) = if authors.len() > max {
+        (&authors[..max], authors.len() - max)
+    } else {
+        (authors, 0)
+    };
+
+    let mut names: Vec<String> = shown.iter().map(|name| escape(name)).collect();
+
+    match others {
+        0 => {}
+        1 => names.push("1 other".to_string()),
+        others => names.push(format!("{} others", others)),
+    }
+
+    match names.pop() {
+        Some(last) => {
+            if names.is_empty() {
+                last
+            } else {
+                format!("{} and {}", names.join(", "), last)
+            }
+        }
+        None => String::new(),
+    }
+}
+
+pub struct Arxiv {
+    client: Client,
+    max_authors: usize,
+}
+
+impl Arxiv {
+    pub fn new(client: Client) -> Arxiv {
+        Arxiv {
+            client,
+            max_authors: 3,
+        }
+    }
+
+    /// Name at most `max_authors` authors of a paper, defaulting to 3.
+    pub fn max_authors(mut self, max_authors: usize) -> Arxiv {
+        self.max_authors = max_authors;
+        self
+    }
+
+    fn card(&self) -> Card {
+        Card::new()
+            .color(super::RED)
+            .footer("arXiv", Some(super::THUMBNAIL))
+    }
+
+    pub fn preview_url(&self, url: &Url, log: &Logger) -> Result<Card> {
+        let id = link::recognize(url).ok_or(ErrorKind::UnrecognizedUrl)?;
+
+        info!(log, "Recognized arXiv URL"; "id" => id.as_str());
+
+        self.preview_paper(&id)
+    }
+
+    pub fn preview_paper(&self, id: &str) -> Result<Card> {
+        let paper = self.client.get_paper(id)?;
+
+        let mut description = String::new();
+
+        if !paper.authors().is_empty() {
+            description.push_str(&abbreviate_authors(paper.authors(), self.max_authors));
+            description.push_str("\n");
+        }
+
+        let mut details = vec![];
+
+        if let Some(category) = paper.primary_category() {
+            details.push(format!("`{}`", category));
+        }
+
+        if let Some(published) = paper.published() {
+            details.push(format!("Submitted {}", published.format("%Y-%m-%d")));
+        }
+
+        if !details.is_empty() {
+            description.push_str(&details.join(" · "));
+            description.push_str("\n");
+        }
+
+        if !description.is_empty() {
+            description.push_str("\n");
+        }
+
+        description.push_str(&truncate(&escape(paper.summary()), ABSTRACT_LENGTH, None));
+
+        let mut card = self.card()
+            .url(format!("https://arxiv.org/abs/{}", id))
+            .title(escape(paper.title()))
+            .description(description);
+
+        if let Some(published) = paper.published() {
+            card = card.timestamp(published);
+        }
+
+        Ok(card)
+    }
+}
+
+impl Preview for Arxiv {
+    fn name(&self) -> &'static str {
+        "arXiv"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        link::recognize(url).is_some()
+    }
+
+    fn preview(&self, url: &Url, log: &Logger) -> errors::Result<Option<Card>> {
+        self.preview_url(url, log).map(Some).map_err(From::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use util::preview_fixture::preview_fixture;
+
+    use super::super::client;
+    use super::{Arxiv, abbreviate_authors};
+
+    #[test]
+    fn test_abbreviate_authors() {
+        let authors: Vec<String> = vec!["A", "B", "C", "D", "E"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        assert_eq!("A", abbreviate_authors(&authors[..1], 3));
+        assert_eq!("A and B", abbreviate_authors(&authors[..2], 3));
+        assert_eq!("A, B and C", abbreviate_authors(&authors[..3], 3));
+        assert_eq!("A, B, C and 1 other", abbreviate_authors(&authors[..4], 3));
+        assert_eq!("A, B, C and 2 others", abbreviate_authors(&authors, 3));
+        assert_eq!("A and 4 others", abbreviate_authors(&authors, 1));
+    }
+
+    #[test]
+    fn test_preview_paper() {
+        let arxiv = Arxiv::new(client::fixtures());
+
+        let card = preview_fixture(&arxiv, "https://arxiv.org/pdf/1706.03762.pdf");
+
+        assert_eq!(Some("Attention Is All You Need"), card.get_title());
+        assert_eq!(Some("https://arxiv.org/abs/1706.03762"), card.get_url());
+        assert_eq!(
+            Some(
+                "Ashish Vaswani, Noam Shazeer, Niki Parmar and 5 others\n\
+                 `cs.CL` · Submitted 2017-06-12\n\n\
+                 The dominant sequence transduction models are based on complex recurrent or \
+                 convolutional neural networks in an encoder-decoder configuration. We propose a \
+                 new simple network architecture, the Transformer, based solely on attention \
+                 mechanisms, dispensing with recurrence and convolutions entirely."
+            ),
+            card.get_description()
+        );
+        assert_eq!("2017-06-12T17:57:34+00:00", card.get_timestamp().unwrap().to_rfc3339());
+
+        let arxiv = Arxiv::new(client::fixtures()).max_authors(1);
+
+        let card = preview_fixture(&arxiv, "https://arxiv.org/abs/1706.03762v5");
+
+        assert!(card.get_description().unwrap().starts_with("Ashish Vaswani and 7 others\n"));
+    }
+}
@@ -1,17 +1,34 @@
 use serenity::client::{Client, Context, EventHandler};
-use serenity::model::{Ready, Message};
+use serenity::model::{Ready, Message, ChannelId};
+use serenity::utils::MessageBuilder;
 
+use chrono::Local;
 use slog::Logger;
 use url::Url;
 use linkify::{LinkFinder, LinkKind};
 
-use preview::Preview;
+use errors::Result;
+use preview::{Preview, Card, Block};
+use util::truncate;
 
 // TODO
 // Put this somewhere useful.
 #[allow(dead_code)]
 pub const EMOJI_POGCHAMP: &'static str = "<:pogchamp:281912440696864769>";
 
+/// The most characters Discord allows in a message.
+pub const MESSAGE_LENGTH: usize = 2000;
+
+/// The most messages sent for each of a card's body blocks.
+pub const MAX_BLOCK_MESSAGES: usize = 3;
+
+/// The characters set aside in each message for formatting that's closed and
+/// reopened where a block is split.
+const SPLIT_OVERHEAD: usize = 100;
+
+/// The most characters Discord allows in an embed's description.
+pub const EMBED_DESCRIPTION_LENGTH: usize = 2048;
+
 pub fn new_client(token: &str, bot: Bot) -> Client<Bot> {
     Client::new(token, bot)
 }
@@ -66,9 +83,7 @@ impl Bot {
 
                     info!(log, "Parsed URL");
 
-                    for previewer in &self.previewers {
-                        previewer.preview(&url, &message, &log);
-                    }
+                    self.dispatch(&url, message, &log);
                 }
                 Err(e) => {
                     error!(log, "Couldn't parse link as a URL";
@@ -77,6 +92,171 @@ impl Bot {
             }
         }
     }
+
+    /// Route the URL to each of the previewers that match it, sending any
+    /// resulting previews to the message's channel.
+    ///
+    /// Fallback previewers are only tried if no site-specific previewer
+    /// matched.
+    fn dispatch(&self, url: &Url, message: &Message, log: &Logger) {
+        let (fallbacks, specific): (Vec<&Box<Preview>>, Vec<&Box<Preview>>) =
+            self.previewers.iter().partition(|p| p.is_fallback());
+
+        if self.dispatch_to(&specific, url, message, log) {
+            return;
+        }
+
+        if !self.dispatch_to(&fallbacks, url, message, log) {
+            info!(log, "No previewer matched");
+        }
+    }
+
+    /// Route the URL to each of the given previewers that match it, returning
+    /// whether any did.
+    fn dispatch_to(
+        &self,
+        previewers: &[&Box<Preview>],
+        url: &Url,
+        message: &Message,
+        log: &Logger,
+    ) -> bool {
+        let mut matched = false;
+
+        for previewer in previewers.iter().filter(|p| p.matches(url)) {
+            matched = true;
+
+            let log = log.new(o!("previewer" => previewer.name()));
+
+            info!(log, "Previewer matched");
+
+            match previewer.preview(url, &log) {
+                Ok(Some(ref card)) if card.is_nsfw() && !self.allows_nsfw(message, &log) => {
+                    info!(log, "Suppressing NSFW preview in non-NSFW channel");
+                }
+                Ok(Some(card)) => {
+                    if let Err(e) = self.send_card(message.channel_id, &card, &log) {
+                        error!(log, "Couldn't send preview"; "error" => e.to_string());
+                    }
+                }
+                Ok(None) => {
+                    info!(log, "Nothing to preview");
+                }
+                Err(e) => {
+                    error!(log, "Couldn't preview URL"; "error" => e.to_string());
+                }
+            }
+        }
+
+        matched
+    }
+
+    /// Whether NSFW content may be posted in the message's channel.
+    ///
+    /// If that can't be determined, it's assumed not to be.
+    fn allows_nsfw(&self, message: &Message, log: &Logger) -> bool {
+        match message.channel_id.get() {
+            Ok(channel) => channel.is_nsfw(),
+            Err(e) => {
+                error!(log, "Couldn't get channel"; "error" => e.to_string());
+                false
+            }
+        }
+    }
+
+    /// Render a `Card` as a Discord embed, followed by a message for each of
+    /// its body blocks.
+    fn send_card(&self, channel_id: ChannelId, card: &Card, log: &Logger) -> Result<()> {
+        let sent = channel_id.send_message(|m| {
+            m.embed(|mut e| {
+                if let Some(title) = card.get_title() {
+                    e = e.title(title);
+                }
+
+                if let Some(url) = card.get_url() {
+                    e = e.url(url);
+                }
+
+                if let Some(description) = card.get_description() {
+                    e = e.description(
+                        truncate::truncate(description, EMBED_DESCRIPTION_LENGTH, None),
+                    );
+                }
+
+                if let Some(color) = card.get_color() {
+                    e = e.color(color);
+                }
+
+                if let Some(thumbnail) = card.get_thumbnail() {
+                    e = e.thumbnail(thumbnail);
+                }
+
+                if let Some(image) = card.get_image() {
+                    e = e.image(image);
+                }
+
+                if let Some(timestamp) = card.get_timestamp() {
+                    e = e.timestamp(&timestamp.with_timezone(&Local));
+                }
+
+                if let Some(footer) = card.get_footer() {
+                    e = e.footer(|mut f| {
+                        if let Some(icon_url) = footer.icon_url() {
+                            f = f.icon_url(icon_url);
+                        }
+
+                        f.text(footer.text())
+                    });
+                }
+
+                e
+            })
+        })?;
+
+        info!(log, "Sent preview embed"; "id" => sent.id.0);
+
+        // Anything longer is cut, so that a huge block can't flood the
+        // channel.
+        let budget = MAX_BLOCK_MESSAGES * (MESSAGE_LENGTH - SPLIT_OVERHEAD);
+
+        for block in card.body() {
+            let content = match *block {
+                Block::Text(ref text) => truncate::truncate(text, budget, card.get_url()),
+                Block::Quote(ref text) => {
+                    MessageBuilder::new()
+                        .push(":speech_left: ")
+                        .push_bold("BEGIN QUOTE")
+                        .push(" :speech_balloon:\n")
+                        .push(&truncate::truncate(text, budget, card.get_url()))
+                        .push("\n")
+                        .push(":speech_left: ")
+                        .push_bold("END QUOTE")
+                        .push(" :speech_balloon:")
+                        .build()
+                }
+                Block::Code { ref language, ref source } => {
+                    // A zero-width space keeps any fences within the source
+                    // from ending the code block early.
+                    let code = format!(
+                        "```{}\n{}\n```",
+                        language.as_ref().map(String::as_str).unwrap_or(""),
+                        source.trim_right_matches('\n').replace("```", "`\u{200B}``")
+                    );
+
+                    truncate::truncate(&code, budget, card.get_url())
+                }
+            };
+
+            let chunks = truncate::split(&content, MESSAGE_LENGTH);
+
+            for chunk in chunks.into_iter().take(MAX_BLOCK_MESSAGES) {
+                let sent = channel_id.say(&chunk)?;
+
+                info!(log, "Sent preview body"; "id" => sent.id.0);
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl EventHandler for Bot {
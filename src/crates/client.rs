@@ -0,0 +1,90 @@
+use util::http::{self, Limits};
+
+use super::{ErrorKind, Result};
+
+use super::docs;
+use super::krate::Crate;
+use super::link::Item;
+
+pub const CRATES_IO_BASE_URL: &'static str = "https://crates.io";
+pub const DOCS_RS_BASE_URL: &'static str = "https://docs.rs";
+
+/// Talks to the crates.io API and fetches documentation from docs.rs.
+pub struct Client {
+    crates_io_base_url: String,
+    docs_rs_base_url: String,
+    limits: Limits,
+}
+
+impl Client {
+    pub fn new() -> Client {
+        Client::with_base_urls(CRATES_IO_BASE_URL, DOCS_RS_BASE_URL)
+    }
+
+    /// Create a client which talks to the sites rooted at the given URLs
+    /// instead of crates.io and docs.rs.
+    pub fn with_base_urls<C, D>(crates_io_base_url: C, docs_rs_base_url: D) -> Client
+    where
+        C: Into<String>,
+        D: Into<String>,
+    {
+        Client {
+            crates_io_base_url: crates_io_base_url.into().trim_right_matches('/').to_string(),
+            docs_rs_base_url: docs_rs_base_url.into().trim_right_matches('/').to_string(),
+            limits: Limits::default(),
+        }
+    }
+
+    pub fn get_crate(&self, name: &str) -> Result<Crate> {
+        let url = format!("{}/api/v1/crates/{}", self.crates_io_base_url, name);
+
+        Ok(http::get_json(&url, self.limits)?)
+    }
+
+    /// The URL of an item's documentation on docs.rs.
+    pub fn docs_url(&self, name: &str, version: Option<&str>, item: &Item) -> String {
+        let (parents, last) = item.path.split_at(item.path.len() - 1);
+
+        let page = if item.kind == "mod" {
+            format!("{}/index.html", item.path.join("/"))
+        } else if parents.is_empty() {
+            format!("{}.{}.html", item.kind, last[0])
+        } else {
+            format!("{}/{}.{}.html", parents.join("/"), item.kind, last[0])
+        };
+
+        format!(
+            "{}/{}/{}/{}",
+            self.docs_rs_base_url,
+            name,
+            version.unwrap_or("latest"),
+            page
+        )
+    }
+
+    /// Fetch the summary of an item's documentation, if it has any.
+    pub fn get_summary(
+        &self,
+        name: &str,
+        version: Option<&str>,
+        item: &Item,
+    ) -> Result<Option<String>> {
+        let url = self.docs_url(name, version, item);
+
+        match http::get_html(&url, self.limits)? {
+            Some(page) => Ok(docs::summary(page.body())),
+            None => bail!(ErrorKind::NotDocumentation(url)),
+        }
+    }
+}
+
+/// A `Client` talking to a server for the fixtures in `tests/fixtures/crates`,
+/// with crates.io's under `crates.io` and docs.rs' under `docs.rs`.
+#[cfg(test)]
+pub fn fixtures() -> Client {
+    use util::fixture_server::FixtureServer;
+
+    let server = FixtureServer::start("crates");
+
+    Client::with_base_urls(server.url("/crates.io"), server.url("/docs.rs"))
+}
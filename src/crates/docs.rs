@@ -0,0 +1,61 @@
+use html5ever::parse_document;
+use html5ever::rcdom::{NodeData, RcDom};
+use html5ever::tendril::TendrilSink;
+
+use util::dom::{has_class, find};
+use util::html_to_markdown;
+
+/// The summary of a rustdoc page as Markdown, i.e. the first paragraph of the
+/// item's documentation, if it has any.
+pub fn summary(html: &str) -> Option<String> {
+    let dom = parse_document(RcDom::default(), Default::default()).one(html);
+
+    // Declarations are also within docblocks, in older versions of rustdoc.
+    let docblock = find(&dom.document, &|node| {
+        has_class(node, "docblock") && !has_class(node, "type-decl") &&
+            !has_class(node, "item-decl")
+    });
+
+    let paragraph = match docblock {
+        Some(docblock) => {
+            find(&docblock, &|node| match node.data {
+                NodeData::Element { ref name, .. } => name.local == local_name!("p"),
+                _ => false,
+            })
+        }
+        None => None,
+    };
+
+    let summary = match paragraph {
+        // Line breaks within the paragraph are insignificant.
+        Some(paragraph) => {
+            html_to_markdown::convert_node(paragraph)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+        None => return None,
+    };
+
+    if summary.is_empty() { None } else { Some(summary) }
+}
+
+#[test]
+fn test_summary() {
+    let html = r#"<html><body><section id="main">
+        <h1 class="fqn">Trait serde::de::Deserialize</h1>
+        <pre class="rust trait">pub trait Deserialize&lt;'de&gt;: Sized { }</pre>
+        <div class="docblock"><p>A <strong>data structure</strong> that can be deserialized
+        from any data format supported by Serde.</p><p>More details.</p></div>
+      </section></body></html>"#;
+
+    assert_eq!(
+        Some(String::from(
+            "A **data structure** that can be deserialized from any data format supported \
+             by Serde.",
+        )),
+        summary(html)
+    );
+
+    assert_eq!(None, summary("<div class='docblock type-decl'><p>decl</p></div>"));
+}
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+
+/// A crate on crates.io, along with all of its versions.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Crate {
+    #[serde(rename = "crate")]
+    info: Info,
+
+    #[serde(default)]
+    versions: Vec<Version>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Info {
+    name: String,
+
+    #[serde(default)]
+    description: Option<String>,
+
+    max_version: String,
+
+    #[serde(default)]
+    downloads: u64,
+
+    #[serde(default)]
+    repository: Option<String>,
+
+    #[serde(default)]
+    documentation: Option<String>,
+
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Version {
+    num: String,
+
+    #[serde(default)]
+    license: Option<String>,
+
+    #[serde(default)]
+    downloads: u64,
+
+    #[serde(default)]
+    yanked: bool,
+
+    created_at: DateTime<Utc>,
+}
+
+impl Crate {
+    pub fn name(&self) -> &str {
+        &self.info.name
+    }
+
+    pub fn url(&self) -> String {
+        format!("https://crates.io/crates/{}", self.info.name)
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.info.description.as_ref().map(|description| description.trim())
+    }
+
+    pub fn max_version(&self) -> &str {
+        &self.info.max_version
+    }
+
+    /// Downloads of all versions of the crate.
+    pub fn downloads(&self) -> u64 {
+        self.info.downloads
+    }
+
+    pub fn repository(&self) -> Option<&str> {
+        self.info.repository.as_ref().map(String::as_str)
+    }
+
+    pub fn documentation(&self) -> Option<&str> {
+        self.info.documentation.as_ref().map(String::as_str)
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.info.updated_at
+    }
+
+    /// Find a version of the crate, e.g. `1.0.10`.
+    pub fn version(&self, num: &str) -> Option<&Version> {
+        self.versions.iter().find(|version| version.num == num)
+    }
+}
+
+impl Version {
+    pub fn num(&self) -> &str {
+        &self.num
+    }
+
+    /// The license of this version as an SPDX expression, e.g. `MIT/Apache-2.0`.
+    pub fn license(&self) -> Option<&str> {
+        self.license.as_ref().map(String::as_str)
+    }
+
+    pub fn downloads(&self) -> u64 {
+        self.downloads
+    }
+
+    pub fn is_yanked(&self) -> bool {
+        self.yanked
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
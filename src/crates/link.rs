@@ -0,0 +1,191 @@
+use url::Url;
+
+/// The crate or documentation that a crates.io or docs.rs URL refers to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// A crate, at its latest version unless one is given.
+    Crate { name: String, version: Option<String> },
+    /// An item within a crate's documentation on docs.rs.
+    Docs {
+        name: String,
+        version: Option<String>,
+        item: Item,
+    },
+}
+
+/// A documented item, e.g. a trait or module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Item {
+    /// The path of the item, e.g. `["serde", "de", "Deserialize"]`.
+    pub path: Vec<String>,
+
+    /// The kind of the item as rustdoc names its pages, e.g. `trait`, or
+    /// `mod` for modules and the crate root.
+    pub kind: String,
+}
+
+/// Top-level pages of docs.rs which look like crate names.
+const RESERVED: &'static [&'static str] =
+    &["about", "crate", "releases", "search", "sitemap.xml", "-"];
+
+/// Whether a version is a specific one, rather than e.g. `latest` or `*`.
+fn parse_version(version: &str) -> Option<String> {
+    if version.starts_with(|c: char| c.is_digit(10)) {
+        Some(version.to_string())
+    } else {
+        None
+    }
+}
+
+/// Figure out which crate or documentation a URL refers to, if any.
+pub fn recognize(url: &Url) -> Option<Target> {
+    let segments = url.path_segments()
+        .map(|segments| segments.collect::<Vec<_>>())
+        .unwrap_or_else(Vec::new);
+
+    // Any trailing slash is significant on docs.rs, since it means that the
+    // last segment is a module rather than an item.
+    let trailing_slash = segments.last() == Some(&"");
+    let segments = segments.into_iter().filter(|s| !s.is_empty()).collect::<Vec<_>>();
+
+    match url.host_str() {
+        Some("crates.io") | Some("www.crates.io") => recognize_crates_io(&segments),
+        Some("docs.rs") => recognize_docs_rs(&segments, trailing_slash),
+        _ => None,
+    }
+}
+
+// https://crates.io/crates/serde/1.0.10
+fn recognize_crates_io(segments: &[&str]) -> Option<Target> {
+    if segments.len() < 2 || segments[0] != "crates" {
+        return None;
+    }
+
+    Some(Target::Crate {
+        name: segments[1].to_string(),
+        version: segments.get(2).and_then(|version| parse_version(version)),
+    })
+}
+
+// https://docs.rs/serde/1.0.10/serde/de/trait.Deserialize.html
+fn recognize_docs_rs(segments: &[&str], trailing_slash: bool) -> Option<Target> {
+    if segments.is_empty() {
+        return None;
+    }
+
+    // https://docs.rs/crate/serde/1.0.10
+    if segments[0] == "crate" {
+        return segments.get(1).map(|name| {
+            Target::Crate {
+                name: name.to_string(),
+                version: segments.get(2).and_then(|version| parse_version(version)),
+            }
+        });
+    }
+
+    if RESERVED.contains(&segments[0]) {
+        return None;
+    }
+
+    let name = segments[0].to_string();
+    let version = segments.get(1).and_then(|version| parse_version(version));
+
+    if segments.len() < 3 {
+        return Some(Target::Crate { name, version });
+    }
+
+    // Rendered source files, e.g. /serde/1.0.10/src/serde/lib.rs.html, which
+    // would otherwise look like items.
+    if segments[2] == "src" {
+        return None;
+    }
+
+    let mut path = segments[2..].iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    let kind = if trailing_slash {
+        String::from("mod")
+    } else {
+        let page = path.pop().expect("path has at least one segment");
+
+        if page == "index.html" {
+            String::from("mod")
+        } else if page.ends_with(".html") {
+            // e.g. trait.Deserialize.html
+            let mut parts = page.trim_right_matches(".html").splitn(2, '.');
+
+            let kind = parts.next().unwrap_or("").to_string();
+
+            match parts.next() {
+                Some(item) if !kind.is_empty() && !item.is_empty() => {
+                    path.push(item.to_string());
+                    kind
+                }
+                _ => return None,
+            }
+        } else {
+            // A module without a trailing slash.
+            path.push(page);
+            String::from("mod")
+        }
+    };
+
+    if path.is_empty() {
+        return None;
+    }
+
+    Some(Target::Docs {
+        name,
+        version,
+        item: Item { path, kind },
+    })
+}
+
+#[test]
+fn test_recognize() {
+    fn krate(version: Option<&str>) -> Option<Target> {
+        Some(Target::Crate {
+            name: String::from("serde"),
+            version: version.map(String::from),
+        })
+    }
+
+    fn docs(version: Option<&str>, path: &[&str], kind: &str) -> Option<Target> {
+        Some(Target::Docs {
+            name: String::from("serde"),
+            version: version.map(String::from),
+            item: Item {
+                path: path.iter().map(|s| s.to_string()).collect(),
+                kind: String::from(kind),
+            },
+        })
+    }
+
+    let cases = vec![
+        ("https://crates.io/crates/serde", krate(None)),
+        ("https://crates.io/crates/serde/1.0.10", krate(Some("1.0.10"))),
+        ("https://crates.io/crates/serde/versions", krate(None)),
+        ("https://docs.rs/serde", krate(None)),
+        ("https://docs.rs/serde/1.0.10", krate(Some("1.0.10"))),
+        ("https://docs.rs/crate/serde/1.0.10", krate(Some("1.0.10"))),
+        ("https://docs.rs/serde/1.0.10/serde/", docs(Some("1.0.10"), &["serde"], "mod")),
+        ("https://docs.rs/serde/*/serde/de/index.html", docs(None, &["serde", "de"], "mod")),
+        ("https://docs.rs/serde/latest/serde/de", docs(None, &["serde", "de"], "mod")),
+        ("https://docs.rs/serde/1.0.10/serde/de/trait.Deserialize.html",
+         docs(Some("1.0.10"), &["serde", "de", "Deserialize"], "trait")),
+        ("https://docs.rs/serde/1.0.10/serde/macro.forward_to_deserialize_any.html#examples",
+         docs(Some("1.0.10"), &["serde", "forward_to_deserialize_any"], "macro")),
+        ("https://docs.rs/serde/1.0.10/src/serde/lib.rs.html", None),
+        ("https://docs.rs/serde/1.0.10/src/serde/de/mod.rs.html#12-20", None),
+        ("https://docs.rs/releases", None),
+        ("https://docs.rs/", None),
+        ("https://crates.io/", None),
+        ("https://crates.io/categories/parsing", None),
+        ("https://example.com/crates/serde", None),
+    ];
+
+    for (url, expected) in cases {
+        let parsed = Url::parse(url).expect("Couldn't parse test URL");
+
+        assert_eq!(expected, recognize(&parsed), "recognizing {}", url);
+    }
+}
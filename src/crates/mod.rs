@@ -0,0 +1,34 @@
+use util::http;
+
+mod client;
+mod krate;
+mod docs;
+mod link;
+mod previewer;
+
+pub use self::client::Client;
+pub use self::link::{Target, Item, recognize};
+pub use self::previewer::Crates;
+
+pub const THUMBNAIL: &'static str = "https://crates.io/favicon.ico";
+pub const COLOR: u64 = 0x3B6837;
+
+error_chain! {
+    links {
+        Http(http::Error, http::ErrorKind);
+    }
+
+    errors {
+        UnrecognizedUrl {
+            description("URL doesn't refer to a crate or its documentation")
+        }
+        VersionNotFound(name: String, version: String) {
+            description("The crate has no such version")
+            display("{} has no version {}", name, version)
+        }
+        NotDocumentation(url: String) {
+            description("The documentation page isn't HTML")
+            display("{} isn't HTML", url)
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use url::Url;
+
+use slog::Logger;
+
+use errors;
+use preview::{Preview, Card};
+use util::html_to_markdown::escape;
+use util::truncate::truncate;
+
+use super::{ErrorKind, Result};
+
+use super::client::Client;
+use super::link::{self, Target, Item};
+
+/// The most characters of a crate's description shown in a preview.
+pub const DESCRIPTION_LENGTH: usize = 500;
+
+/// Format a number with commas between groups of digits, e.g. "12,345".
+fn group_digits(number: u64) -> String {
+    let digits = number.to_string();
+    let mut grouped = String::new();
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+
+        grouped.push(digit);
+    }
+
+    grouped
+}
+
+/// Describe the kind of an item the way rustdoc titles its pages.
+fn describe_kind(item: &Item) -> &str {
+    match item.kind.as_str() {
+        "mod" if item.path.len() == 1 => "Crate",
+        "mod" => "Module",
+        "fn" => "Function",
+        "struct" => "Struct",
+        "enum" => "Enum",
+        "union" => "Union",
+        "trait" => "Trait",
+        "macro" => "Macro",
+        "derive" => "Derive Macro",
+        "attr" => "Attribute Macro",
+        "type" => "Type Definition",
+        "constant" => "Constant",
+        "static" => "Static",
+        "primitive" => "Primitive Type",
+        "keyword" => "Keyword",
+        kind => kind,
+    }
+}
+
+pub struct Crates {
+    client: Client,
+}
+
+impl Crates {
+    pub fn new(client: Client) -> Crates {
+        Crates { client }
+    }
+
+    fn card(&self) -> Card {
+        Card::new().color(super::COLOR)
+    }
+
+    pub fn preview_url(&self, url: &Url, log: &Logger) -> Result<Card> {
+        let target = link::recognize(url).ok_or(ErrorKind::UnrecognizedUrl)?;
+
+        info!(log, "Recognized crate URL"; "target" => format!("{:?}", target));
+
+        match target {
+            Target::Crate { name, version } => {
+                self.preview_crate(url, &name, version.as_ref().map(String::as_str))
+            }
+            Target::Docs {
+                name,
+                version,
+                item,
+            } => self.preview_docs(url, &name, version.as_ref().map(String::as_str), &item),
+        }
+    }
+
+    pub fn preview_crate(&self, url: &Url, name: &str, version: Option<&str>) -> Result<Card> {
+        let krate = self.client.get_crate(name)?;
+
+        // A link to a particular version is about that version rather than
+        // the crate as a whole.
+        let is_versioned = version.is_some();
+        let num = version.unwrap_or(krate.max_version());
+
+        let version = krate.version(num).ok_or_else(|| {
+            ErrorKind::VersionNotFound(name.to_string(), num.to_string())
+        })?;
+
+        let mut description = String::new();
+
+        if let Some(text) = krate.description() {
+            description.push_str(&truncate(&escape(text), DESCRIPTION_LENGTH, None));
+            description.push_str("\n\n");
+        }
+
+        if is_versioned {
+            description.push_str(&format!(
+                "**{}** downloads of this version.",
+                group_digits(version.downloads())
+            ));
+        } else {
+            description.push_str(&format!(
+                "**{}** downloads.",
+                group_digits(krate.downloads())
+            ));
+        }
+
+        if let Some(license) = version.license() {
+            description.push_str(&format!(" License: `{}`.", license));
+        }
+
+        if let Some(repository) = krate.repository() {
+            description.push_str(&format!(" [Repository]({}).", repository));
+        }
+
+        if version.is_yanked() {
+            description.push_str(" **Yanked**.");
+        }
+
+        if version.num() != krate.max_version() {
+            description.push_str(&format!(" The latest version is `{}`.", krate.max_version()));
+        }
+
+        // Links to docs.rs should stay there.
+        let url = if url.host_str() == Some("docs.rs") {
+            url.to_string()
+        } else {
+            krate.url()
+        };
+
+        Ok(self.card()
+            .url(url)
+            .title(format!("{} {}", krate.name(), version.num()))
+            .description(description)
+            .footer("crates.io", Some(super::THUMBNAIL))
+            .timestamp(version.created_at()))
+    }
+
+    pub fn preview_docs(
+        &self,
+        url: &Url,
+        name: &str,
+        version: Option<&str>,
+        item: &Item,
+    ) -> Result<Card> {
+        let summary = self.client.get_summary(name, version, item)?;
+
+        let mut description = format!(
+            "{} in **{}** `{}`",
+            describe_kind(item),
+            escape(name),
+            version.unwrap_or("latest")
+        );
+
+        if let Some(summary) = summary {
+            description.push_str("\n\n");
+            description.push_str(&truncate(&summary, DESCRIPTION_LENGTH, None));
+        }
+
+        Ok(self.card()
+            .url(url.as_str())
+            .title(item.path.join("::"))
+            .description(description)
+            .footer("Docs.rs", None))
+    }
+}
+
+impl Preview for Crates {
+    fn name(&self) -> &'static str {
+        "Crates"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        link::recognize(url).is_some()
+    }
+
+    fn preview(&self, url: &Url, log: &Logger) -> errors::Result<Option<Card>> {
+        self.preview_url(url, log).map(Some).map_err(From::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors;
+    use util::preview_fixture::{preview_fixture, try_preview_fixture};
+
+    use super::super::{client, ErrorKind};
+    use super::{Crates, group_digits};
+
+    #[test]
+    fn test_preview_crate() {
+        let crates = Crates::new(client::fixtures());
+
+        let card = preview_fixture(&crates, "https://crates.io/crates/serde");
+
+        assert_eq!(Some("serde 1.0.11"), card.get_title());
+        assert_eq!(Some("https://crates.io/crates/serde"), card.get_url());
+        assert_eq!(
+            Some(
+                "A generic serialization/deserialization framework\n\n\
+                 **12,345,678** downloads. License: `MIT/Apache-2.0`. \
+                 [Repository](https://github.com/serde-rs/serde).",
+            ),
+            card.get_description()
+        );
+
+        let card = preview_fixture(&crates, "https://docs.rs/crate/serde/1.0.10");
+
+        assert_eq!(Some("serde 1.0.10"), card.get_title());
+        assert_eq!(Some("https://docs.rs/crate/serde/1.0.10"), card.get_url());
+        assert!(card.get_description().unwrap().ends_with(
+            "**123,456** downloads of this version. License: `MIT/Apache-2.0`. \
+             [Repository](https://github.com/serde-rs/serde). \
+             **Yanked**. The latest version is `1.0.11`.",
+        ));
+
+        let error = try_preview_fixture(&crates, "https://crates.io/crates/serde/0.1.0")
+            .expect_err("Previewed a version that doesn't exist");
+
+        match *error.kind() {
+            errors::ErrorKind::Crates(ErrorKind::VersionNotFound(..)) => {}
+            ref kind => panic!("Unexpected error: {}", kind),
+        }
+    }
+
+    #[test]
+    fn test_preview_docs() {
+        let crates = Crates::new(client::fixtures());
+
+        let url = "https://docs.rs/serde/1.0.10/serde/de/trait.Deserialize.html";
+        let card = preview_fixture(&crates, url);
+
+        assert_eq!(Some("serde::de::Deserialize"), card.get_title());
+        assert_eq!(
+            Some(
+                "Trait in **serde** `1.0.10`\n\n\
+                 A **data structure** that can be deserialized from any data format supported by \
+                 Serde.",
+            ),
+            card.get_description()
+        );
+    }
+
+    #[test]
+    fn test_group_digits() {
+        assert_eq!("0", group_digits(0));
+        assert_eq!("999", group_digits(999));
+        assert_eq!("1,000", group_digits(1000));
+        assert_eq!("12,345,678", group_digits(12345678));
+    }
+}
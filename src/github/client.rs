@@ -0,0 +1,91 @@
+use serde::de::DeserializeOwned;
+
+use util::http::{self, Limits};
+
+use super::Result;
+
+use super::repo::Repo;
+use super::issue::Issue;
+use super::pull::Pull;
+use super::commit::Commit;
+
+pub const API_BASE_URL: &'static str = "https://api.github.com";
+pub const RAW_BASE_URL: &'static str = "https://raw.githubusercontent.com";
+
+/// Talks to the GitHub API, and fetches raw files from repositories.
+pub struct Client {
+    api_base_url: String,
+    raw_base_url: String,
+    limits: Limits,
+}
+
+impl Client {
+    pub fn new() -> Client {
+        Client::with_base_urls(API_BASE_URL, RAW_BASE_URL)
+    }
+
+    /// Create a client which talks to an API rooted at `api_base_url`, and
+    /// fetches files from `raw_base_url`, instead of GitHub's.
+    pub fn with_base_urls<A, R>(api_base_url: A, raw_base_url: R) -> Client
+    where
+        A: Into<String>,
+        R: Into<String>,
+    {
+        Client {
+            api_base_url: api_base_url.into().trim_right_matches('/').to_string(),
+            raw_base_url: raw_base_url.into().trim_right_matches('/').to_string(),
+            // Commits include their diffs, which can be large.
+            limits: Limits {
+                max_size: 4 * 1024 * 1024,
+                ..Limits::default()
+            },
+        }
+    }
+
+    fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = format!("{}{}", self.api_base_url, path);
+
+        Ok(http::get_json(&url, self.limits)?)
+    }
+
+    pub fn get_repo(&self, owner: &str, repo: &str) -> Result<Repo> {
+        self.get(&format!("/repos/{}/{}", owner, repo))
+    }
+
+    pub fn get_issue(&self, owner: &str, repo: &str, number: u64) -> Result<Issue> {
+        self.get(&format!("/repos/{}/{}/issues/{}", owner, repo, number))
+    }
+
+    pub fn get_pull(&self, owner: &str, repo: &str, number: u64) -> Result<Pull> {
+        self.get(&format!("/repos/{}/{}/pulls/{}", owner, repo, number))
+    }
+
+    pub fn get_commit(&self, owner: &str, repo: &str, sha: &str) -> Result<Commit> {
+        self.get(&format!("/repos/{}/{}/commits/{}", owner, repo, sha))
+    }
+
+    /// Fetch the contents of a file at the given branch, tag or commit.
+    ///
+    /// `path` is expected to already be percent-encoded.
+    pub fn get_file(
+        &self,
+        owner: &str,
+        repo: &str,
+        reference: &str,
+        path: &str,
+    ) -> Result<String> {
+        let url = format!("{}/{}/{}/{}/{}", self.raw_base_url, owner, repo, reference, path);
+
+        Ok(http::get(&url, self.limits)?.body().to_string())
+    }
+}
+
+/// A `Client` talking to a server for the fixtures in `tests/fixtures/github`.
+#[cfg(test)]
+pub fn fixtures() -> Client {
+    use util::fixture_server::FixtureServer;
+
+    let server = FixtureServer::start("github");
+
+    Client::with_base_urls(server.url("/api"), server.url("/raw"))
+}
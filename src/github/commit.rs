@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+
+use super::issue::User;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Commit {
+    sha: String,
+    html_url: String,
+    commit: Details,
+
+    /// The GitHub account of the author, if their email is associated with
+    /// one.
+    #[serde(default)]
+    author: Option<User>,
+
+    #[serde(default)]
+    stats: Stats,
+}
+
+/// The git data of a commit, as opposed to GitHub's.
+#[derive(Clone, Debug, Deserialize)]
+struct Details {
+    message: String,
+    author: Signature,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Signature {
+    name: String,
+    date: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+struct Stats {
+    #[serde(default)]
+    additions: u64,
+
+    #[serde(default)]
+    deletions: u64,
+}
+
+impl Commit {
+    /// The abbreviated SHA, as GitHub displays it.
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..::std::cmp::min(7, self.sha.len())]
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn message(&self) -> &str {
+        &self.commit.message
+    }
+
+    /// The first line of the commit message.
+    pub fn summary(&self) -> &str {
+        self.commit.message.lines().next().unwrap_or("")
+    }
+
+    /// The name of the author as recorded in git.
+    pub fn author_name(&self) -> &str {
+        &self.commit.author.name
+    }
+
+    pub fn author(&self) -> Option<&User> {
+        self.author.as_ref()
+    }
+
+    pub fn authored_at(&self) -> DateTime<Utc> {
+        self.commit.author.date
+    }
+
+    pub fn additions(&self) -> u64 {
+        self.stats.additions
+    }
+
+    pub fn deletions(&self) -> u64 {
+        self.stats.deletions
+    }
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+
+/// A GitHub account.
+#[derive(Clone, Debug, Deserialize)]
+pub struct User {
+    login: String,
+    avatar_url: String,
+}
+
+impl User {
+    pub fn login(&self) -> &str {
+        &self.login
+    }
+
+    pub fn avatar_url(&self) -> &str {
+        &self.avatar_url
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Label {
+    name: String,
+}
+
+impl Label {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Issue {
+    number: u64,
+    title: String,
+    html_url: String,
+
+    /// Either `open` or `closed`.
+    state: String,
+
+    user: User,
+
+    #[serde(default)]
+    labels: Vec<Label>,
+
+    #[serde(default)]
+    comments: u64,
+
+    created_at: DateTime<Utc>,
+}
+
+impl Issue {
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state == "open"
+    }
+
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    pub fn comments(&self) -> u64 {
+        self.comments
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
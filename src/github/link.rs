@@ -0,0 +1,209 @@
+use url::Url;
+
+/// The thing on GitHub that a URL refers to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    Repo { owner: String, repo: String },
+    Issue { owner: String, repo: String, number: u64 },
+    Pull { owner: String, repo: String, number: u64 },
+    Commit { owner: String, repo: String, sha: String },
+    Blob {
+        owner: String,
+        repo: String,
+        reference: String,
+        /// The path of the file within the repository, still percent-encoded.
+        path: String,
+        lines: Option<Lines>,
+    },
+}
+
+/// An inclusive, 1-based range of lines selected in a file, e.g. `#L10-L20`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lines {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Top-level pages of github.com which look like user or organization names.
+const RESERVED_OWNERS: &'static [&'static str] = &[
+    "about",
+    "apps",
+    "collections",
+    "contact",
+    "explore",
+    "features",
+    "issues",
+    "login",
+    "marketplace",
+    "new",
+    "notifications",
+    "orgs",
+    "organizations",
+    "pricing",
+    "pulls",
+    "search",
+    "settings",
+    "site",
+    "topics",
+    "trending",
+];
+
+/// Figure out which repository, issue, pull request, commit or file a URL
+/// refers to, if any.
+pub fn recognize(url: &Url) -> Option<Target> {
+    match url.host_str() {
+        Some("github.com") | Some("www.github.com") => {}
+        _ => return None,
+    }
+
+    let segments = url.path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect::<Vec<_>>())
+        .unwrap_or_else(Vec::new);
+
+    if segments.len() < 2 || RESERVED_OWNERS.contains(&segments[0]) {
+        return None;
+    }
+
+    let owner = segments[0].to_string();
+    let repo = segments[1].trim_right_matches(".git").to_string();
+
+    if segments.len() == 2 {
+        return Some(Target::Repo { owner, repo });
+    }
+
+    match segments[2] {
+        // Any branch, tag or directory within the repository.
+        "tree" => Some(Target::Repo { owner, repo }),
+        "issues" if segments.len() == 4 => {
+            parse_number(segments[3]).map(|number| Target::Issue { owner, repo, number })
+        }
+        // A commit viewed within a pull request is still that commit.
+        "pull" if segments.len() == 6 && segments[4] == "commits" => {
+            Some(Target::Commit {
+                owner,
+                repo,
+                sha: segments[5].to_string(),
+            })
+        }
+        // e.g. /pull/1/files
+        "pull" if segments.len() >= 4 => {
+            parse_number(segments[3]).map(|number| Target::Pull { owner, repo, number })
+        }
+        "commit" if segments.len() == 4 => {
+            Some(Target::Commit {
+                owner,
+                repo,
+                sha: segments[3].to_string(),
+            })
+        }
+        // The reference is assumed not to contain any slashes, since there's
+        // no telling where it would end and the path would begin.
+        "blob" if segments.len() >= 5 => {
+            Some(Target::Blob {
+                owner,
+                repo,
+                reference: segments[3].to_string(),
+                path: segments[4..].join("/"),
+                lines: url.fragment().and_then(parse_lines),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn parse_number(number: &str) -> Option<u64> {
+    number.parse().ok()
+}
+
+/// Parse a line selection fragment, e.g. `L10` or `L10-L20`.
+fn parse_lines(fragment: &str) -> Option<Lines> {
+    let mut bounds = fragment.splitn(2, '-').map(|bound| {
+        if bound.starts_with('L') {
+            bound[1..].parse::<usize>().ok()
+        } else {
+            None
+        }
+    });
+
+    let start = match bounds.next() {
+        Some(Some(start)) if start > 0 => start,
+        _ => return None,
+    };
+
+    let end = match bounds.next() {
+        Some(Some(end)) if end >= start => end,
+        Some(_) => return None,
+        None => start,
+    };
+
+    Some(Lines { start, end })
+}
+
+#[test]
+fn test_recognize() {
+    fn repo() -> Target {
+        Target::Repo {
+            owner: String::from("rust-lang"),
+            repo: String::from("rust"),
+        }
+    }
+
+    fn blob(path: &str, lines: Option<Lines>) -> Target {
+        Target::Blob {
+            owner: String::from("rust-lang"),
+            repo: String::from("rust"),
+            reference: String::from("master"),
+            path: String::from(path),
+            lines,
+        }
+    }
+
+    let commit = Target::Commit {
+        owner: String::from("rust-lang"),
+        repo: String::from("rust"),
+        sha: String::from("f1e2d3c"),
+    };
+
+    let cases = vec![
+        ("https://github.com/rust-lang/rust", Some(repo())),
+        ("https://www.github.com/rust-lang/rust/", Some(repo())),
+        ("https://github.com/rust-lang/rust.git", Some(repo())),
+        ("https://github.com/rust-lang/rust/tree/master/src", Some(repo())),
+        ("https://github.com/rust-lang/rust/issues/42",
+         Some(Target::Issue {
+             owner: String::from("rust-lang"),
+             repo: String::from("rust"),
+             number: 42,
+         })),
+        ("https://github.com/rust-lang/rust/pull/43/files",
+         Some(Target::Pull {
+             owner: String::from("rust-lang"),
+             repo: String::from("rust"),
+             number: 43,
+         })),
+        ("https://github.com/rust-lang/rust/commit/f1e2d3c", Some(commit.clone())),
+        ("https://github.com/rust-lang/rust/pull/43/commits/f1e2d3c", Some(commit.clone())),
+        ("https://github.com/rust-lang/rust/blob/master/src/lib.rs",
+         Some(blob("src/lib.rs", None))),
+        ("https://github.com/rust-lang/rust/blob/master/src/lib.rs#L10",
+         Some(blob("src/lib.rs", Some(Lines { start: 10, end: 10 })))),
+        ("https://github.com/rust-lang/rust/blob/master/src/lib.rs#L10-L20",
+         Some(blob("src/lib.rs", Some(Lines { start: 10, end: 20 })))),
+        ("https://github.com/rust-lang/rust/blob/master/src/lib.rs#L20-L10",
+         Some(blob("src/lib.rs", None))),
+        ("https://github.com/rust-lang/rust/blob/master/my%20file.rs#readme",
+         Some(blob("my%20file.rs", None))),
+        ("https://github.com/rust-lang", None),
+        ("https://github.com/settings/profile", None),
+        ("https://github.com/rust-lang/rust/issues", None),
+        ("https://github.com/rust-lang/rust/issues/abc", None),
+        ("https://github.com/rust-lang/rust/blob/master", None),
+        ("https://gitlab.com/rust-lang/rust", None),
+    ];
+
+    for (url, expected) in cases {
+        let parsed = Url::parse(url).expect("Couldn't parse test URL");
+
+        assert_eq!(expected, recognize(&parsed), "recognizing {}", url);
+    }
+}
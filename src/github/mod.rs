@@ -0,0 +1,32 @@
+use util::http;
+
+mod client;
+mod repo;
+mod issue;
+mod pull;
+mod commit;
+mod link;
+mod previewer;
+
+pub use self::client::Client;
+pub use self::link::{Target, Lines, recognize};
+pub use self::previewer::GitHub;
+
+pub const THUMBNAIL: &'static str = "https://assets-cdn.github.com/favicon.ico";
+pub const COLOR: u64 = 0x24292E;
+
+error_chain! {
+    links {
+        Http(http::Error, http::ErrorKind);
+    }
+
+    errors {
+        UnrecognizedUrl {
+            description("URL doesn't refer to anything on GitHub")
+        }
+        LinesOutOfRange(line: usize, lines: usize) {
+            description("Selected lines are beyond the end of the file")
+            display("Line {} is beyond the end of the file, which has {} lines", line, lines)
+        }
+    }
+}
@@ -0,0 +1,550 @@
+use std::cmp;
+
+use url::Url;
+use url::percent_encoding::percent_decode;
+
+use slog::Logger;
+
+use errors;
+use preview::{Preview, Card, Block};
+use util::html_to_markdown::escape;
+use util::plural;
+use util::truncate::{truncate, excerpt, CODE_LENGTH};
+
+use super::{ErrorKind, Result};
+
+use super::client::Client;
+use super::issue::Label;
+use super::link::{self, Target, Lines};
+
+/// The most lines of a file shown in a preview, whether or not a range of
+/// lines was selected.
+pub const MAX_LINES: usize = 30;
+
+/// The most characters of a description or commit message shown in a preview.
+pub const DESCRIPTION_LENGTH: usize = 500;
+
+/// The language to highlight a file as, going by its name.
+///
+/// These are the names that Discord's highlighter, highlight.js, goes by.
+fn language_for(path: &str) -> Option<&'static str> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+
+    match name {
+        "Makefile" => return Some("makefile"),
+        "Dockerfile" => return Some("dockerfile"),
+        _ => {}
+    }
+
+    let extension = match name.rfind('.') {
+        Some(index) => &name[index + 1..],
+        None => return None,
+    };
+
+    let language = match extension {
+        "rs" => "rust",
+        "toml" => "toml",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hh" | "hpp" => "cpp",
+        "cs" => "cs",
+        "go" => "go",
+        "hs" => "haskell",
+        "java" => "java",
+        "js" | "mjs" => "javascript",
+        "ts" => "typescript",
+        "json" => "json",
+        "kt" => "kotlin",
+        "lua" => "lua",
+        "md" | "markdown" => "markdown",
+        "php" => "php",
+        "py" => "python",
+        "rb" => "ruby",
+        "scala" => "scala",
+        "sh" | "bash" => "bash",
+        "sql" => "sql",
+        "swift" => "swift",
+        "html" | "xml" => "xml",
+        "css" => "css",
+        "yml" | "yaml" => "yaml",
+        _ => return None,
+    };
+
+    Some(language)
+}
+
+/// Whether a file is binary going by its name, in which case it isn't worth
+/// fetching.
+fn is_binary_path(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+
+    let extension = match name.rfind('.') {
+        Some(index) => name[index + 1..].to_lowercase(),
+        None => return false,
+    };
+
+    let binary = [
+        "png", "jpg", "jpeg", "gif", "bmp", "ico", "webp", "pdf", "zip", "gz", "tgz", "xz",
+        "bz2", "7z", "tar", "jar", "class", "exe", "dll", "so", "dylib", "a", "o", "rlib",
+        "wasm", "woff", "woff2", "ttf", "otf", "mp3", "mp4", "ogg", "wav", "bin",
+    ];
+
+    binary.contains(&extension.as_str())
+}
+
+/// Render text as inline code, with a delimiter longer than any run of
+/// backticks within it, e.g. "``a`b``".
+fn inline_code(text: &str) -> String {
+    let longest = text.split(|c: char| c != '`').map(str::len).max().unwrap_or(0);
+    let delimiter = "`".repeat(longest + 1);
+
+    // A backtick next to the delimiter would run into it.
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{} {} {}", delimiter, text, delimiter)
+    } else {
+        format!("{}{}{}", delimiter, text, delimiter)
+    }
+}
+
+/// Render labels as inline code, e.g. "`bug`, `help wanted`".
+fn describe_labels(labels: &[Label]) -> String {
+    labels
+        .iter()
+        .map(|label| format!("`{}`", label.name().replace('`', "'")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub struct GitHub {
+    client: Client,
+}
+
+impl GitHub {
+    pub fn new(client: Client) -> GitHub {
+        GitHub { client }
+    }
+
+    fn card(&self) -> Card {
+        Card::new()
+            .color(super::COLOR)
+            .footer("GitHub", Some(super::THUMBNAIL))
+    }
+
+    pub fn preview_url(&self, url: &Url, log: &Logger) -> Result<Card> {
+        let target = link::recognize(url).ok_or(ErrorKind::UnrecognizedUrl)?;
+
+        info!(log, "Recognized GitHub URL"; "target" => format!("{:?}", target));
+
+        match target {
+            Target::Repo { owner, repo } => self.preview_repo(&owner, &repo),
+            Target::Issue { owner, repo, number } => self.preview_issue(&owner, &repo, number),
+            Target::Pull { owner, repo, number } => self.preview_pull(&owner, &repo, number),
+            Target::Commit { owner, repo, sha } => self.preview_commit(&owner, &repo, &sha),
+            Target::Blob {
+                owner,
+                repo,
+                reference,
+                path,
+                lines,
+            } => self.preview_blob(url, &owner, &repo, &reference, &path, lines),
+        }
+    }
+
+    pub fn preview_repo(&self, owner: &str, repo: &str) -> Result<Card> {
+        let repo = self.client.get_repo(owner, repo)?;
+
+        let mut description = String::new();
+
+        if let Some(text) = repo.description() {
+            description.push_str(&truncate(&escape(text), DESCRIPTION_LENGTH, None));
+            description.push_str("\n\n");
+        }
+
+        description.push_str(&format!(
+            "{}. {}.",
+            plural(repo.stars(), "star"),
+            plural(repo.forks(), "fork")
+        ));
+
+        if let Some(language) = repo.language() {
+            description.push_str(&format!(" Written in **{}**.", language));
+        }
+
+        if repo.is_fork() {
+            description.push_str(" A fork.");
+        }
+
+        if repo.is_archived() {
+            description.push_str(" Archived.");
+        }
+
+        Ok(self.card()
+            .url(repo.html_url())
+            .title(repo.full_name())
+            .description(description)
+            .timestamp(repo.pushed_at()))
+    }
+
+    pub fn preview_issue(&self, owner: &str, repo: &str, number: u64) -> Result<Card> {
+        let issue = self.client.get_issue(owner, repo, number)?;
+
+        let mut description = format!(
+            "**{}** issue by **{}**. {}.",
+            if issue.is_open() { "Open" } else { "Closed" },
+            escape(issue.user().login()),
+            plural(issue.comments(), "comment")
+        );
+
+        if !issue.labels().is_empty() {
+            description.push_str(&format!("\nLabels: {}", describe_labels(issue.labels())));
+        }
+
+        Ok(self.card()
+            .url(issue.html_url())
+            .title(format!("Issue #{}: {}", issue.number(), issue.title()))
+            .description(description)
+            .thumbnail(issue.user().avatar_url())
+            .timestamp(issue.created_at()))
+    }
+
+    pub fn preview_pull(&self, owner: &str, repo: &str, number: u64) -> Result<Card> {
+        let pull = self.client.get_pull(owner, repo, number)?;
+
+        let state = if pull.is_merged() {
+            "Merged"
+        } else if pull.is_open() {
+            "Open"
+        } else {
+            "Closed"
+        };
+
+        let mut description = format!(
+            "**{}** pull request by **{}**. {} changing {} (+{} −{}). {}.",
+            state,
+            escape(pull.user().login()),
+            plural(pull.commits(), "commit"),
+            plural(pull.changed_files(), "file"),
+            pull.additions(),
+            pull.deletions(),
+            plural(pull.comments(), "comment")
+        );
+
+        if !pull.labels().is_empty() {
+            description.push_str(&format!("\nLabels: {}", describe_labels(pull.labels())));
+        }
+
+        Ok(self.card()
+            .url(pull.html_url())
+            .title(format!("Pull Request #{}: {}", pull.number(), pull.title()))
+            .description(description)
+            .thumbnail(pull.user().avatar_url())
+            .timestamp(pull.created_at()))
+    }
+
+    pub fn preview_commit(&self, owner: &str, repo: &str, sha: &str) -> Result<Card> {
+        let commit = self.client.get_commit(owner, repo, sha)?;
+
+        let author = match commit.author() {
+            Some(user) => escape(user.login()),
+            None => escape(commit.author_name()),
+        };
+
+        let description = format!(
+            "{}\n\nby **{}** (+{} −{})",
+            truncate(&escape(commit.message().trim()), DESCRIPTION_LENGTH, None),
+            author,
+            commit.additions(),
+            commit.deletions()
+        );
+
+        let mut card = self.card()
+            .url(commit.html_url())
+            .title(format!("Commit {} to {}/{}", commit.short_sha(), owner, repo))
+            .description(description)
+            .timestamp(commit.authored_at());
+
+        if let Some(user) = commit.author() {
+            card = card.thumbnail(user.avatar_url());
+        }
+
+        Ok(card)
+    }
+
+    pub fn preview_blob(
+        &self,
+        url: &Url,
+        owner: &str,
+        repo: &str,
+        reference: &str,
+        path: &str,
+        lines: Option<Lines>,
+    ) -> Result<Card> {
+        let decoded = percent_decode(path.as_bytes()).decode_utf8_lossy().into_owned();
+
+        // Files with nothing to show get a card saying what they are.
+        let describe = |what: &str| {
+            self.card()
+                .url(url.as_str())
+                .title(format!("{}/{}: {}", owner, repo, decoded))
+                .description(format!(
+                    "{} at {} is {}",
+                    inline_code(&decoded),
+                    inline_code(reference),
+                    what
+                ))
+        };
+
+        if is_binary_path(&decoded) {
+            return Ok(describe("a binary file"));
+        }
+
+        let contents = self.client.get_file(owner, repo, reference, path)?;
+
+        if contents.contains('\0') {
+            return Ok(describe("a binary file"));
+        }
+
+        let all = contents.lines().collect::<Vec<_>>();
+
+        if all.is_empty() {
+            return Ok(describe("an empty file"));
+        }
+
+        let path: &str = &decoded;
+
+        let (start, selected_end) = match lines {
+            Some(lines) => (lines.start, cmp::min(lines.end, all.len())),
+            None => (1, all.len()),
+        };
+
+        ensure!(start <= all.len(), ErrorKind::LinesOutOfRange(start, all.len()));
+
+        let excerpt = excerpt(&all[start - 1..selected_end], MAX_LINES, CODE_LENGTH);
+        let end = start + excerpt.lines - 1;
+
+        let location = format!("{} at {}", inline_code(path), inline_code(reference));
+
+        let mut description = if start == end {
+            format!("Line **{}** of {}", start, location)
+        } else {
+            format!("Lines **{}** to **{}** of {}", start, end, location)
+        };
+
+        if excerpt.cut_line {
+            description.push_str(&format!(", cut at {} characters", CODE_LENGTH));
+        } else if end < selected_end {
+            description.push_str(&format!(
+                ", the first {} of {} lines",
+                excerpt.lines,
+                selected_end - start + 1
+            ));
+        }
+
+        let block = Block::Code {
+            language: language_for(path).map(String::from),
+            source: excerpt.source,
+        };
+
+        Ok(self.card()
+            .url(url.as_str())
+            .title(format!("{}/{}: {}", owner, repo, path))
+            .description(description)
+            .block(block))
+    }
+}
+
+impl Preview for GitHub {
+    fn name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        link::recognize(url).is_some()
+    }
+
+    fn preview(&self, url: &Url, log: &Logger) -> errors::Result<Option<Card>> {
+        self.preview_url(url, log).map(Some).map_err(From::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors;
+    use preview::Block;
+    use util::preview_fixture::{preview_fixture, try_preview_fixture};
+    use util::truncate::CODE_LENGTH;
+
+    use super::super::{client, ErrorKind};
+    use super::{GitHub, language_for, inline_code};
+
+    #[test]
+    fn test_preview_repo() {
+        let github = GitHub::new(client::fixtures());
+
+        let card = preview_fixture(&github, "https://github.com/rust-lang/rust");
+
+        assert_eq!(Some("rust-lang/rust"), card.get_title());
+        assert_eq!(Some("https://github.com/rust-lang/rust"), card.get_url());
+        assert_eq!(
+            Some(
+                "A safe, concurrent, practical language.\n\n\
+                 **26741** stars. **5344** forks. Written in **Rust**.",
+            ),
+            card.get_description()
+        );
+    }
+
+    #[test]
+    fn test_preview_issue() {
+        let github = GitHub::new(client::fixtures());
+
+        let card = preview_fixture(&github, "https://github.com/rust-lang/rust/issues/42");
+
+        assert_eq!(
+            Some("Issue #42: Make the tutorial less of a wall of text"),
+            card.get_title()
+        );
+        assert_eq!(
+            Some(
+                "**Closed** issue by **some\\_contributor**. **3** comments.\n\
+                 Labels: `A-docs`, `E-easy`",
+            ),
+            card.get_description()
+        );
+        assert_eq!(
+            Some("https://avatars1.githubusercontent.com/u/1294?v=4"),
+            card.get_thumbnail()
+        );
+    }
+
+    #[test]
+    fn test_preview_pull() {
+        let github = GitHub::new(client::fixtures());
+
+        let card = preview_fixture(&github, "https://github.com/rust-lang/rust/pull/43/files");
+
+        assert_eq!(
+            Some("Pull Request #43: Split the tutorial into sections"),
+            card.get_title()
+        );
+        assert_eq!(
+            Some(
+                "**Merged** pull request by **another-contributor**. **2** commits changing \
+                 **1** file (+120 −45). **1** comment.",
+            ),
+            card.get_description()
+        );
+    }
+
+    #[test]
+    fn test_preview_commit() {
+        let github = GitHub::new(client::fixtures());
+
+        let card = preview_fixture(&github, "https://github.com/rust-lang/rust/commit/f1e2d3c");
+
+        assert_eq!(Some("Commit f1e2d3c to rust-lang/rust"), card.get_title());
+        assert_eq!(
+            Some(
+                "Split the tutorial into sections\n\nEach section now gets its own \\*page\\*.\n\n\
+                 by **another-contributor** (+120 −45)",
+            ),
+            card.get_description()
+        );
+    }
+
+    #[test]
+    fn test_preview_blob() {
+        let github = GitHub::new(client::fixtures());
+
+        let url = "https://github.com/rust-lang/rust/blob/master/src/lib.rs#L3-L6";
+        let card = preview_fixture(&github, url);
+
+        assert_eq!(Some("rust-lang/rust: src/lib.rs"), card.get_title());
+        assert_eq!(
+            Some("Lines **3** to **6** of `src/lib.rs` at `master`"),
+            card.get_description()
+        );
+        assert_eq!(
+            &[
+                Block::Code {
+                    language: Some(String::from("rust")),
+                    source: String::from(
+                        "/// Add two numbers.\npub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}",
+                    ),
+                },
+            ],
+            card.body()
+        );
+
+        let url = "https://github.com/rust-lang/rust/blob/master/src/lib.rs#L20";
+        let error = try_preview_fixture(&github, url)
+            .expect_err("Previewed lines beyond the end of the file");
+
+        match *error.kind() {
+            errors::ErrorKind::GitHub(ErrorKind::LinesOutOfRange(20, 11)) => {}
+            ref kind => panic!("Unexpected error: {}", kind),
+        }
+    }
+
+    #[test]
+    fn test_preview_blob_limits() {
+        let github = GitHub::new(client::fixtures());
+
+        let url = "https://github.com/rust-lang/rust/blob/master/src/empty.rs";
+        let card = preview_fixture(&github, url);
+
+        assert_eq!(
+            Some("`src/empty.rs` at `master` is an empty file"),
+            card.get_description()
+        );
+        assert!(card.body().is_empty());
+
+        let url = "https://github.com/rust-lang/rust/blob/master/src/minified.js";
+        let card = preview_fixture(&github, url);
+
+        assert_eq!(
+            Some("Line **1** of `src/minified.js` at `master`, cut at 1900 characters"),
+            card.get_description()
+        );
+
+        match card.body()[0] {
+            Block::Code { ref source, .. } => assert_eq!(CODE_LENGTH, source.chars().count()),
+            ref block => panic!("Expected code, got {:?}", block),
+        }
+
+        // Binary files are recognized by their extension without being
+        // fetched, or else by their contents.
+        let url = "https://github.com/rust-lang/rust/blob/master/src/logo.png";
+        let card = preview_fixture(&github, url);
+
+        assert_eq!(
+            Some("`src/logo.png` at `master` is a binary file"),
+            card.get_description()
+        );
+        assert!(card.body().is_empty());
+
+        let url = "https://github.com/rust-lang/rust/blob/master/src/compiled";
+        let card = preview_fixture(&github, url);
+
+        assert_eq!(
+            Some("`src/compiled` at `master` is a binary file"),
+            card.get_description()
+        );
+        assert!(card.body().is_empty());
+    }
+
+    #[test]
+    fn test_inline_code() {
+        assert_eq!("`src/lib.rs`", inline_code("src/lib.rs"));
+        assert_eq!("``a`b``", inline_code("a`b"));
+        assert_eq!("``` ``a` ```", inline_code("``a`"));
+    }
+
+    #[test]
+    fn test_language_for() {
+        assert_eq!(Some("rust"), language_for("src/main.rs"));
+        assert_eq!(Some("makefile"), language_for("Makefile"));
+        assert_eq!(Some("cpp"), language_for("include/a.b/thing.hpp"));
+        assert_eq!(None, language_for("LICENSE"));
+        assert_eq!(None, language_for("notes.weird"));
+    }
+}
@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+
+use super::issue::{User, Label};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Pull {
+    number: u64,
+    title: String,
+    html_url: String,
+
+    /// Either `open` or `closed`, including when merged.
+    state: String,
+
+    #[serde(default)]
+    merged: bool,
+
+    user: User,
+
+    #[serde(default)]
+    labels: Vec<Label>,
+
+    #[serde(default)]
+    comments: u64,
+
+    #[serde(default)]
+    commits: u64,
+
+    #[serde(default)]
+    additions: u64,
+
+    #[serde(default)]
+    deletions: u64,
+
+    #[serde(default)]
+    changed_files: u64,
+
+    created_at: DateTime<Utc>,
+}
+
+impl Pull {
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state == "open"
+    }
+
+    pub fn is_merged(&self) -> bool {
+        self.merged
+    }
+
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    pub fn comments(&self) -> u64 {
+        self.comments
+    }
+
+    pub fn commits(&self) -> u64 {
+        self.commits
+    }
+
+    pub fn additions(&self) -> u64 {
+        self.additions
+    }
+
+    pub fn deletions(&self) -> u64 {
+        self.deletions
+    }
+
+    pub fn changed_files(&self) -> u64 {
+        self.changed_files
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
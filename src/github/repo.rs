@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Repo {
+    full_name: String,
+    html_url: String,
+
+    #[serde(default)]
+    description: Option<String>,
+
+    #[serde(default)]
+    language: Option<String>,
+
+    #[serde(default)]
+    stargazers_count: u64,
+
+    #[serde(default)]
+    forks_count: u64,
+
+    #[serde(default)]
+    fork: bool,
+
+    #[serde(default)]
+    archived: bool,
+
+    pushed_at: DateTime<Utc>,
+}
+
+impl Repo {
+    /// The name of the repository including its owner, e.g. `rust-lang/rust`.
+    pub fn full_name(&self) -> &str {
+        &self.full_name
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_ref().map(String::as_str)
+    }
+
+    /// The predominant language of the repository.
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_ref().map(String::as_str)
+    }
+
+    pub fn stars(&self) -> u64 {
+        self.stargazers_count
+    }
+
+    pub fn forks(&self) -> u64 {
+        self.forks_count
+    }
+
+    pub fn is_fork(&self) -> bool {
+        self.fork
+    }
+
+    pub fn is_archived(&self) -> bool {
+        self.archived
+    }
+
+    /// When anything was last pushed to the repository.
+    pub fn pushed_at(&self) -> DateTime<Utc> {
+        self.pushed_at
+    }
+}
@@ -0,0 +1,206 @@
+use std::cmp;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use super::Result;
+
+use super::client::Client;
+use super::item::Item;
+use super::user::User;
+
+/// How long fetched items are served for when their mutable data, such as
+/// score and kids, is going to be used.
+pub const DEFAULT_MUTABLE_TTL: u64 = 5 * 60;
+
+/// How long fetched items are served for when only their immutable data, such
+/// as title and parent, is going to be used.
+pub const DEFAULT_IMMUTABLE_TTL: u64 = 24 * 60 * 60;
+
+/// The most items kept at once.
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+/// A `Client` which keeps fetched `Item`s in memory.
+///
+/// Items are stored once, but are considered fresh for different amounts of
+/// time depending on whether they're requested through `get_item` or
+/// `get_item_immutable`. This way walking up a comment's parent chain rarely
+/// hits the API, while scores and reply counts stay reasonably current.
+pub struct Cache<C> {
+    client: C,
+    items: Mutex<HashMap<i64, Entry>>,
+    mutable_ttl: Duration,
+    immutable_ttl: Duration,
+    capacity: usize,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+struct Entry {
+    item: Item,
+    fetched: Instant,
+}
+
+/// A snapshot of a `Cache`'s hit and miss counters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    hits: usize,
+    misses: usize,
+}
+
+impl Stats {
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+}
+
+impl<C: Client> Cache<C> {
+    pub fn new(client: C) -> Cache<C> {
+        Cache::with_ttls(
+            client,
+            Duration::from_secs(DEFAULT_MUTABLE_TTL),
+            Duration::from_secs(DEFAULT_IMMUTABLE_TTL),
+        )
+    }
+
+    pub fn with_ttls(client: C, mutable_ttl: Duration, immutable_ttl: Duration) -> Cache<C> {
+        Cache {
+            client,
+            items: Mutex::new(HashMap::new()),
+            mutable_ttl,
+            immutable_ttl,
+            capacity: DEFAULT_CAPACITY,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Set the most items kept at once.
+    pub fn capacity(mut self, capacity: usize) -> Cache<C> {
+        self.capacity = cmp::max(capacity, 1);
+        self
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn lookup(&self, id: i64, ttl: Duration) -> Option<Item> {
+        let items = self.items.lock().expect("HN cache lock was poisoned");
+
+        items.get(&id).and_then(|entry| if entry.fetched.elapsed() < ttl {
+            Some(entry.item.clone())
+        } else {
+            None
+        })
+    }
+
+    fn insert(&self, id: i64, item: Item) {
+        let mut items = self.items.lock().expect("HN cache lock was poisoned");
+
+        if items.len() >= self.capacity && !items.contains_key(&id) {
+            // Nothing outlives the immutable TTL, so first drop anything that
+            // can no longer be served, and only then the oldest item.
+            let ttl = self.immutable_ttl;
+            items.retain(|_, entry| entry.fetched.elapsed() < ttl);
+
+            if items.len() >= self.capacity {
+                let oldest = items
+                    .iter()
+                    .min_by_key(|&(_, entry)| entry.fetched)
+                    .map(|(&id, _)| id);
+
+                if let Some(oldest) = oldest {
+                    items.remove(&oldest);
+                }
+            }
+        }
+
+        items.insert(
+            id,
+            Entry {
+                item,
+                fetched: Instant::now(),
+            },
+        );
+    }
+
+    fn fetch(&self, id: i64, ttl: Duration) -> Result<Item> {
+        if let Some(item) = self.lookup(id, ttl) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+
+            return Ok(item);
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        let item = self.client.get_item(id)?;
+
+        self.insert(id, item.clone());
+
+        Ok(item)
+    }
+}
+
+impl<C: Client> Client for Cache<C> {
+    fn get_item(&self, id: i64) -> Result<Item> {
+        self.fetch(id, self.mutable_ttl)
+    }
+
+    fn get_item_immutable(&self, id: i64) -> Result<Item> {
+        self.fetch(id, self.immutable_ttl)
+    }
+
+    fn get_user(&self, id: &str) -> Result<User> {
+        self.client.get_user(id)
+    }
+
+    fn cache_stats(&self) -> Option<Stats> {
+        Some(self.stats())
+    }
+}
+
+#[test]
+fn test_cache_ttls() {
+    use super::client;
+
+    let cache = Cache::with_ttls(
+        client::fixtures(),
+        Duration::from_secs(0),
+        Duration::from_secs(60),
+    );
+
+    cache.get_item(14774167).expect("Couldn't get item");
+    cache.get_item(14774167).expect("Couldn't get item");
+
+    assert_eq!(0, cache.stats().hits());
+    assert_eq!(2, cache.stats().misses());
+
+    cache.get_item_immutable(14774167).expect("Couldn't get item");
+
+    assert_eq!(1, cache.stats().hits());
+    assert_eq!(2, cache.stats().misses());
+}
+
+#[test]
+fn test_cache_capacity() {
+    use super::client;
+
+    let cache = Cache::new(client::fixtures()).capacity(1);
+
+    cache.get_item_immutable(14774167).expect("Couldn't get item");
+    cache.get_item_immutable(14775602).expect("Couldn't get item");
+    cache.get_item_immutable(14775602).expect("Couldn't get item");
+    cache.get_item_immutable(14774167).expect("Couldn't get item");
+
+    assert_eq!(1, cache.stats().hits());
+    assert_eq!(3, cache.stats().misses());
+}
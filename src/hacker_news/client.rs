@@ -0,0 +1,117 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use reqwest;
+use serde::de::DeserializeOwned;
+use serde_json;
+
+use super::{Result, ResultExt};
+
+use super::cache::Stats;
+use super::item::Item;
+use super::user::User;
+
+pub const API_BASE_URL: &'static str = "https://hacker-news.firebaseio.com/v0";
+
+/// A source of Hacker News API data.
+///
+/// This exists so that everything that talks to the HN API can be pointed
+/// somewhere other than the live API, e.g. fixtures during tests.
+pub trait Client: Send + Sync {
+    fn get_item(&self, id: i64) -> Result<Item>;
+
+    /// Fetch an item whose mutable data, such as its score and kids, isn't
+    /// going to be used.
+    ///
+    /// This lets caching clients serve older data, e.g. when walking up a
+    /// comment's parent chain.
+    fn get_item_immutable(&self, id: i64) -> Result<Item> {
+        self.get_item(id)
+    }
+
+    fn get_user(&self, id: &str) -> Result<User>;
+
+    /// The hit and miss counters of the client, if it's a caching client.
+    fn cache_stats(&self) -> Option<Stats> {
+        None
+    }
+}
+
+/// A `Client` which talks to the HN Firebase API over HTTP.
+pub struct HttpClient {
+    base_url: String,
+}
+
+impl HttpClient {
+    pub fn new() -> HttpClient {
+        HttpClient::with_base_url(API_BASE_URL)
+    }
+
+    /// Create a client which talks to an API rooted at `base_url` instead of
+    /// the official one, e.g. `http://localhost:8080/v0`.
+    pub fn with_base_url<S: Into<String>>(base_url: S) -> HttpClient {
+        HttpClient {
+            base_url: base_url.into().trim_right_matches('/').to_string(),
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+impl Client for HttpClient {
+    fn get_item(&self, id: i64) -> Result<Item> {
+        let url = format!("{}/item/{}.json", self.base_url, id);
+
+        reqwest::get(&url)?
+            .json::<Item>()
+            .chain_err(|| "Couldn't GET HN API endpoint")
+    }
+
+    fn get_user(&self, id: &str) -> Result<User> {
+        let url = format!("{}/user/{}.json", self.base_url, id);
+
+        reqwest::get(&url)?
+            .json::<User>()
+            .chain_err(|| "Couldn't GET HN API endpoint")
+    }
+}
+
+/// A `Client` which serves items from JSON fixtures on the file system.
+///
+/// Item `id` is read from `<root>/<id>.json` and user `id` is read from
+/// `<root>/user/<id>.json`.
+pub struct FixtureClient {
+    root: PathBuf,
+}
+
+impl FixtureClient {
+    pub fn new<P: Into<PathBuf>>(root: P) -> FixtureClient {
+        FixtureClient { root: root.into() }
+    }
+
+    fn read<T: DeserializeOwned>(&self, path: &Path) -> Result<T> {
+        let file = File::open(&path)
+            .chain_err(|| format!("Couldn't open fixture {}", path.display()))?;
+
+        serde_json::from_reader(file)
+            .chain_err(|| format!("Couldn't parse fixture {}", path.display()))
+    }
+}
+
+impl Client for FixtureClient {
+    fn get_item(&self, id: i64) -> Result<Item> {
+        self.read(&self.root.join(format!("{}.json", id)))
+    }
+
+    fn get_user(&self, id: &str) -> Result<User> {
+        self.read(&self.root.join("user").join(format!("{}.json", id)))
+    }
+}
+
+/// A `FixtureClient` serving the fixtures in `tests/fixtures/hacker_news`.
+#[cfg(test)]
+pub fn fixtures() -> FixtureClient {
+    FixtureClient::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/hacker_news"))
+}
@@ -4,21 +4,30 @@ use chrono_tz::Tz;
 
 use super::{Error, ErrorKind, Result};
 
+use super::client::Client;
 use super::story::Story;
 use super::item::Item;
-
-use util;
+use super::thread;
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct Comment {
     id: i64,
+
+    #[serde(default)]
     by: String,
 
     #[serde(default)]
     kids: Vec<i64>,
 
+    #[serde(default)]
     text: String,
 
+    #[serde(default)]
+    deleted: bool,
+
+    #[serde(default)]
+    dead: bool,
+
     #[serde(with = "ts_seconds")]
     time: DateTime<Utc>,
 
@@ -26,29 +35,44 @@ pub struct Comment {
 }
 
 impl Comment {
-    /// Find the root ancestor of this item.
+    /// Find the root ancestor of this comment.
     ///
-    /// This only really applies to `Item::Comment`s, hence the `Option`.
-    pub fn get_story(&self) -> Result<Story> {
-        let parent = match Item::get(self.parent) {
-            Ok(parent) => parent,
-            Err(e) => return Err(e),
-        };
-
-        match parent {
-            Item::Story(story) => Ok(story),
-            Item::Comment(comment) => comment.get_story(),
-        }
+    /// This is usually a `Story`, but comments can also be made on polls and
+    /// jobs. Gives up after `thread::DEFAULT_MAX_DEPTH` ancestors.
+    pub fn get_root(&self, client: &Client) -> Result<Item> {
+        let mut ancestors = thread::ancestors(client, self, thread::DEFAULT_MAX_DEPTH)?;
+
+        Ok(ancestors.pop().expect("ancestors always ends with the root"))
+    }
+
+    /// Find the root story of this comment.
+    pub fn get_story(&self, client: &Client) -> Result<Story> {
+        self.get_root(client)?
+            .story()
+            .ok_or_else(|| Error::from_kind(ErrorKind::NotAStory))
     }
 
     pub fn id(&self) -> i64 {
         self.id
     }
 
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+
+    /// Whether this has been flagged or killed by moderators.
+    pub fn is_dead(&self) -> bool {
+        self.dead
+    }
+
     pub fn by(&self) -> &str {
         &self.by
     }
 
+    pub fn parent(&self) -> i64 {
+        self.parent
+    }
+
     pub fn kids(&self) -> &[i64] {
         &self.kids
     }
@@ -57,10 +81,6 @@ impl Comment {
         &self.text
     }
 
-    pub fn text_as_markdown(&self) -> Result<String> {
-        util::html_to_markdown::convert(&self.text).map_err(From::from)
-    }
-
     pub fn time(&self) -> DateTime<Utc> {
         self.time
     }
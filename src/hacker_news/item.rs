@@ -1,10 +1,11 @@
-use reqwest;
-use url::Url;
-
-use super::{Error, ErrorKind, Result, ResultExt};
+use super::Result;
 
+use super::client::Client;
 use super::comment::Comment;
 use super::story::Story;
+use super::job::Job;
+use super::poll::Poll;
+use super::pollopt::PollOpt;
 
 // API Reference: https://github.com/HackerNews/API
 //
@@ -13,6 +14,10 @@ use super::story::Story;
 // empty string in the event that the field isn't present. This is because the
 // HN Firebase API entirely omits the field when it's not relevant. The same is
 // done for comments with no kids (i.e. replies).
+//
+// Deleted and dead items are missing most of their fields, such as `by`,
+// `text` and `title`, so those are defaulted as well. Check `is_deleted` and
+// `is_dead` before relying on them.
 
 #[derive(Clone, Debug, Deserialize)]
 #[serde(tag = "type")]
@@ -20,51 +25,103 @@ use super::story::Story;
 pub enum Item {
     Comment(Comment),
     Story(Story),
+    Job(Job),
+    Poll(Poll),
+    PollOpt(PollOpt),
 }
 
 impl Item {
-    pub fn get(id: i64) -> Result<Item> {
-        let url = format!("https://hacker-news.firebaseio.com/v0/item/{}.json", id);
+    pub fn get(client: &Client, id: i64) -> Result<Item> {
+        client.get_item(id)
+    }
 
-        reqwest::get(&url)?
-            .json::<Item>()
-            .chain_err(|| "Couldn't GET HN API endpoint")
+    pub fn id(&self) -> i64 {
+        match *self {
+            Item::Comment(ref comment) => comment.id(),
+            Item::Story(ref story) => story.id(),
+            Item::Job(ref job) => job.id(),
+            Item::Poll(ref poll) => poll.id(),
+            Item::PollOpt(ref poll_opt) => poll_opt.id(),
+        }
     }
 
-    // https://news.ycombinator.com/item?id=14817557
-    pub fn from_url(url: &Url) -> Result<Item> {
-        if let Some(host) = url.host_str() {
-            ensure!(host == "news.ycombinator.com", ErrorKind::InvalidHost);
-        } else {
-            bail!(ErrorKind::InvalidHost);
+    pub fn url(&self) -> String {
+        format!("https://news.ycombinator.com/item?id={}", self.id())
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        match *self {
+            Item::Comment(ref comment) => comment.is_deleted(),
+            Item::Story(ref story) => story.is_deleted(),
+            Item::Job(ref job) => job.is_deleted(),
+            Item::Poll(ref poll) => poll.is_deleted(),
+            Item::PollOpt(ref poll_opt) => poll_opt.is_deleted(),
         }
+    }
 
-        ensure!(url.path() == "/item", ErrorKind::InvalidPath);
+    /// Whether this item has been flagged or killed by moderators.
+    pub fn is_dead(&self) -> bool {
+        match *self {
+            Item::Comment(ref comment) => comment.is_dead(),
+            Item::Story(ref story) => story.is_dead(),
+            Item::Job(ref job) => job.is_dead(),
+            Item::Poll(ref poll) => poll.is_dead(),
+            Item::PollOpt(ref poll_opt) => poll_opt.is_dead(),
+        }
+    }
+
+    /// The ids of this item's direct replies, in ranked order.
+    pub fn kids(&self) -> &[i64] {
+        match *self {
+            Item::Comment(ref comment) => comment.kids(),
+            Item::Story(ref story) => story.kids(),
+            Item::Poll(ref poll) => poll.kids(),
+            Item::Job(..) | Item::PollOpt(..) => &[],
+        }
+    }
 
-        url.query_pairs()
-            .find(|param| param.0 == "id")
-            .ok_or(Error::from_kind(ErrorKind::MissingId))
-            .and_then(|param| {
-                param
-                    .1
-                    .parse::<i64>()
-                    .map_err(|e| Error::from_kind(ErrorKind::ParseInt(e)))
-                    .chain_err(|| "Couldn't parse URL id as an i64 integer")
-            })
-            .and_then(Item::get)
+    /// The title of this item, if it's the kind of item that has one.
+    pub fn title(&self) -> Option<&str> {
+        match *self {
+            Item::Story(ref story) => Some(story.title()),
+            Item::Job(ref job) => Some(job.title()),
+            Item::Poll(ref poll) => Some(poll.title()),
+            Item::Comment(..) | Item::PollOpt(..) => None,
+        }
     }
 
     pub fn comment(self) -> Option<Comment> {
         match self {
             Item::Comment(comment) => Some(comment),
-            Item::Story(..) => None,
+            _ => None,
         }
     }
 
     pub fn story(self) -> Option<Story> {
         match self {
             Item::Story(story) => Some(story),
-            Item::Comment(..) => None,
+            _ => None,
+        }
+    }
+
+    pub fn job(self) -> Option<Job> {
+        match self {
+            Item::Job(job) => Some(job),
+            _ => None,
+        }
+    }
+
+    pub fn poll(self) -> Option<Poll> {
+        match self {
+            Item::Poll(poll) => Some(poll),
+            _ => None,
+        }
+    }
+
+    pub fn poll_opt(self) -> Option<PollOpt> {
+        match self {
+            Item::PollOpt(poll_opt) => Some(poll_opt),
+            _ => None,
         }
     }
 
@@ -83,28 +140,41 @@ impl Item {
             false
         }
     }
-}
 
-// TODO
-// To test this stuff we could use reqwest_mock's StubClient
-// https://docs.rs/reqwest_mock/0.2.0/reqwest_mock/client/struct.StubClient.html
-//
-// It would be cool if:
-//
-//   * IntoBody was implemented for File
-//   * it could be setup to redirect all requests to files on the fs, i.e.
-//     `somesite.com/some/resource/123` would map to
-//     `tests/fixtures/somesite.com/some/resource/123`
+    pub fn is_job(&self) -> bool {
+        if let Item::Job { .. } = *self {
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn is_poll(&self) -> bool {
+        if let Item::Poll { .. } = *self {
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn is_poll_opt(&self) -> bool {
+        if let Item::PollOpt { .. } = *self {
+            true
+        } else {
+            false
+        }
+    }
+}
 
 #[test]
 fn test_item_parse() {
     use chrono::{NaiveDateTime, DateTime, Utc};
-    use std::path::Path;
 
-    let _fixture =
-        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/hacker_news/13028891.json");
+    use super::client;
 
-    let item = Item::get(13028891).expect("Couldn't get the item");
+    let client = client::fixtures();
+
+    let item = Item::get(&client, 13028891).expect("Couldn't get the item");
     let comment = item.comment().expect("Not a comment!");
 
     assert_eq!(13028891, comment.id());
@@ -127,11 +197,55 @@ fn test_item_parse() {
 
 #[test]
 fn test_get_story() {
-    let deep = Item::get(14775602).expect("Couldn't get item");
+    use super::client;
+
+    let client = client::fixtures();
+
+    let deep = Item::get(&client, 14775602).expect("Couldn't get item");
     let root = deep.comment()
         .expect("Not a comment!")
-        .get_story()
+        .get_story(&client)
         .expect("Item didn't have a parent");
 
     assert_eq!(14774167, root.id());
 }
+
+#[test]
+fn test_get_story_through_deleted() {
+    use super::client;
+
+    let client = client::fixtures();
+
+    let comment = Item::get(&client, 15023406)
+        .expect("Couldn't get item")
+        .comment()
+        .expect("Not a comment!");
+
+    let parent = Item::get(&client, 15023220).expect("Couldn't get parent");
+
+    assert!(parent.is_deleted());
+    assert_eq!(
+        14774167,
+        comment.get_story(&client).expect("Couldn't get story").id()
+    );
+}
+
+#[test]
+fn test_poll_parse() {
+    use super::client;
+
+    let client = client::fixtures();
+
+    let poll = Item::get(&client, 126809)
+        .expect("Couldn't get item")
+        .poll()
+        .expect("Not a poll!");
+
+    assert_eq!(&[126810, 126811, 126812], poll.parts());
+
+    let options = poll.get_options(&client).expect("Couldn't get poll options");
+    let scores = options.iter().map(|o| o.score()).collect::<Vec<_>>();
+
+    assert_eq!(vec![335, 163, 73], scores);
+    assert!(options.iter().all(|o| o.poll() == poll.id()));
+}
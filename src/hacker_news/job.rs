@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use chrono::serde::ts_seconds;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Job {
+    id: i64,
+
+    #[serde(default)]
+    by: String,
+
+    #[serde(default)]
+    text: String,
+
+    #[serde(default)]
+    deleted: bool,
+
+    #[serde(default)]
+    dead: bool,
+
+    #[serde(with = "ts_seconds")]
+    time: DateTime<Utc>,
+
+    #[serde(default)]
+    title: String,
+
+    #[serde(default)]
+    url: String,
+
+    #[serde(default)]
+    score: i64,
+}
+
+impl Job {
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+
+    /// Whether this has been flagged or killed by moderators.
+    pub fn is_dead(&self) -> bool {
+        self.dead
+    }
+
+    pub fn by(&self) -> &str {
+        &self.by
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The URL of the job posting itself, if it's hosted off-site.
+    pub fn url(&self) -> Option<&str> {
+        if self.url.is_empty() {
+            None
+        } else {
+            Some(&self.url)
+        }
+    }
+
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    /// The URL of the job's page on Hacker News.
+    pub fn item_url(&self) -> String {
+        format!("https://news.ycombinator.com/item?id={}", self.id())
+    }
+}
@@ -0,0 +1,176 @@
+use url::Url;
+
+/// The thing on Hacker News that a URL refers to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    Item(i64),
+    User(String),
+}
+
+/// Figure out which item or user a URL refers to, if any.
+///
+/// This understands the various pages of news.ycombinator.com as well as the
+/// API, HN Search and a few popular alternative front-ends.
+pub fn recognize(url: &Url) -> Option<Target> {
+    let host = match url.host_str() {
+        Some(host) => host.trim_left_matches("www."),
+        None => return None,
+    };
+
+    let segments = url.path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect::<Vec<_>>())
+        .unwrap_or_else(Vec::new);
+
+    match host {
+        "news.ycombinator.com" => recognize_site(url),
+        "hacker-news.firebaseio.com" => recognize_api(&segments),
+        "hn.algolia.com" => recognize_algolia(url, &segments),
+        "hckrnws.com" if segments.len() == 2 && segments[0] == "stories" => {
+            parse_item(segments[1])
+        }
+        // These are single-page apps which route on the fragment, e.g.
+        // https://hn.premii.com/#/comments/14774167
+        "hn.premii.com" | "hackerweb.app" => {
+            let fragment = url.fragment().unwrap_or("");
+            let segments = fragment
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>();
+
+            if segments.len() != 2 {
+                return None;
+            }
+
+            match segments[0] {
+                "comments" | "item" => parse_item(segments[1]),
+                "user" => parse_user(segments[1]),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn parse_item(id: &str) -> Option<Target> {
+    id.parse::<i64>().ok().map(Target::Item)
+}
+
+/// HN usernames are only ever letters, digits, dashes and underscores, and
+/// anything else shouldn't make it into an API path.
+fn parse_user(id: &str) -> Option<Target> {
+    let is_valid = !id.is_empty() && id.chars().all(|c| c.is_digit(36) || c == '-' || c == '_');
+
+    if is_valid {
+        Some(Target::User(id.to_string()))
+    } else {
+        None
+    }
+}
+
+fn query_id(url: &Url) -> Option<String> {
+    url.query_pairs()
+        .find(|param| param.0 == "id")
+        .map(|param| param.1.into_owned())
+        .and_then(|id| if id.is_empty() { None } else { Some(id) })
+}
+
+// https://news.ycombinator.com/item?id=14774167#14775347
+fn recognize_site(url: &Url) -> Option<Target> {
+    match url.path() {
+        "/item" | "/reply" | "/context" | "/edit" => {
+            // Comment permalinks within a thread are fragments on the story.
+            if let Some(target) = url.fragment().and_then(parse_item) {
+                return Some(target);
+            }
+
+            query_id(url).and_then(|id| parse_item(&id))
+        }
+        "/user" | "/threads" | "/submitted" | "/favorites" => {
+            query_id(url).and_then(|id| parse_user(&id))
+        }
+        _ => None,
+    }
+}
+
+// https://hacker-news.firebaseio.com/v0/item/14774167.json
+fn recognize_api(segments: &[&str]) -> Option<Target> {
+    if segments.len() != 3 || segments[0] != "v0" {
+        return None;
+    }
+
+    let id = segments[2].trim_right_matches(".json");
+
+    match segments[1] {
+        "item" => parse_item(id),
+        "user" => parse_user(id),
+        _ => None,
+    }
+}
+
+// https://hn.algolia.com/story/14774167/the-mercurial-coding-style
+fn recognize_algolia(url: &Url, segments: &[&str]) -> Option<Target> {
+    if segments.len() >= 2 && segments[0] == "story" {
+        return parse_item(segments[1]);
+    }
+
+    if segments.len() == 4 && segments[0] == "api" && segments[1] == "v1" {
+        return match segments[2] {
+            "items" => parse_item(segments[3]),
+            "users" => parse_user(segments[3]),
+            _ => None,
+        };
+    }
+
+    url.query_pairs()
+        .find(|param| param.0 == "story")
+        .and_then(|param| parse_item(&param.1))
+}
+
+#[test]
+fn test_recognize() {
+    let cases = vec![
+        ("https://news.ycombinator.com/item?id=14774167", Some(Target::Item(14774167))),
+        ("http://news.ycombinator.com/item?id=14774167", Some(Target::Item(14774167))),
+        ("https://www.news.ycombinator.com/item?id=14774167", Some(Target::Item(14774167))),
+        ("https://news.ycombinator.com/item?id=14774167&p=2", Some(Target::Item(14774167))),
+        ("https://news.ycombinator.com/item?id=14774167#14775347", Some(Target::Item(14775347))),
+        ("https://news.ycombinator.com/item?id=14774167#reply", Some(Target::Item(14774167))),
+        ("https://news.ycombinator.com/reply?id=14775347&goto=item%3Fid%3D14774167",
+         Some(Target::Item(14775347))),
+        ("https://news.ycombinator.com/context?id=14775347", Some(Target::Item(14775347))),
+        ("https://news.ycombinator.com/user?id=pg", Some(Target::User(String::from("pg")))),
+        ("https://news.ycombinator.com/threads?id=pg", Some(Target::User(String::from("pg")))),
+        ("https://news.ycombinator.com/submitted?id=pg", Some(Target::User(String::from("pg")))),
+        ("https://hacker-news.firebaseio.com/v0/item/14774167.json",
+         Some(Target::Item(14774167))),
+        ("https://hacker-news.firebaseio.com/v0/user/pg.json",
+         Some(Target::User(String::from("pg")))),
+        ("https://hn.algolia.com/story/14774167/the-mercurial-coding-style",
+         Some(Target::Item(14774167))),
+        ("https://hn.algolia.com/?query=mercurial&story=14774167", Some(Target::Item(14774167))),
+        ("https://hn.algolia.com/api/v1/items/14774167", Some(Target::Item(14774167))),
+        ("https://hn.algolia.com/api/v1/users/pg", Some(Target::User(String::from("pg")))),
+        ("https://hckrnws.com/stories/14774167", Some(Target::Item(14774167))),
+        ("https://hn.premii.com/#/comments/14774167", Some(Target::Item(14774167))),
+        ("https://hackerweb.app/#/item/14774167", Some(Target::Item(14774167))),
+        ("https://hackerweb.app/#/user/pg", Some(Target::User(String::from("pg")))),
+        ("https://hackerweb.app/#/user/p.g", None),
+        ("https://hacker-news.firebaseio.com/v0/user/%2E%2E.json", None),
+        ("https://hn.algolia.com/api/v1/users/p%3Fg", None),
+        ("https://news.ycombinator.com/", None),
+        ("https://news.ycombinator.com/news?p=2", None),
+        ("https://news.ycombinator.com/item", None),
+        ("https://news.ycombinator.com/item?id=abc", None),
+        ("https://news.ycombinator.com/user?id=", None),
+        ("https://news.ycombinator.com/user?id=..%2Fitem%2F1", None),
+        ("https://news.ycombinator.com/user?id=p%20g", None),
+        ("https://hn.algolia.com/?query=mercurial", None),
+        ("https://example.com/item?id=14774167", None),
+    ];
+
+    for (url, expected) in cases {
+        let parsed = Url::parse(url).expect("Couldn't parse test URL");
+
+        assert_eq!(expected, recognize(&parsed), "recognizing {}", url);
+    }
+}
@@ -1,16 +1,25 @@
 use std::num;
 
 use reqwest;
-use serenity;
-
-use util::html_to_markdown;
 
+mod cache;
+mod client;
 mod story;
 mod comment;
 mod item;
+mod link;
+mod job;
+mod poll;
+mod pollopt;
+mod user;
+mod thread;
 mod previewer;
 
+pub use self::cache::Cache;
+pub use self::client::{Client, HttpClient, FixtureClient};
+pub use self::link::{Target, recognize};
 pub use self::previewer::HackerNews;
+pub use self::thread::{Walker, Node};
 
 pub const THUMBNAIL: &'static str = "https://news.ycombinator.com/y18.gif";
 pub const ORANGE: u64 = 0xFF6600;
@@ -19,22 +28,18 @@ error_chain! {
     foreign_links {
         ParseInt(num::ParseIntError);
         Reqwest(reqwest::Error);
-        Serenity(serenity::Error);
-    }
-
-    links {
-        HtmlParse(html_to_markdown::Error, html_to_markdown::ErrorKind);
     }
 
     errors {
-        InvalidHost {
-            description("Host is not that of Hacker News (news.ycombinator.com)")
+        UnrecognizedUrl {
+            description("URL doesn't refer to a Hacker News item or user")
         }
-        InvalidPath {
-            description("URL path is not correct (/item)")
+        NotAStory {
+            description("Item is not a story")
         }
-        MissingId {
-            description("URL is missing the id query parameter (id={id})")
+        ThreadTooDeep(max_depth: usize) {
+            description("Comment thread is too deep to walk")
+            display("Comment thread is deeper than {} levels", max_depth)
         }
         TimeZoneParse(e: String) {
             description("Could not parse the IANA timezone identifier")
@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use chrono::serde::ts_seconds;
+
+use super::Result;
+
+use super::client::Client;
+use super::item::Item;
+use super::pollopt::PollOpt;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Poll {
+    id: i64,
+
+    #[serde(default)]
+    by: String,
+
+    #[serde(default)]
+    kids: Vec<i64>,
+
+    #[serde(default)]
+    parts: Vec<i64>,
+
+    #[serde(default)]
+    text: String,
+
+    #[serde(default)]
+    deleted: bool,
+
+    #[serde(default)]
+    dead: bool,
+
+    #[serde(with = "ts_seconds")]
+    time: DateTime<Utc>,
+
+    #[serde(default)]
+    title: String,
+
+    #[serde(default)]
+    score: i64,
+
+    #[serde(default)]
+    descendants: i64,
+}
+
+impl Poll {
+    /// Fetch this poll's options, in the order in which they're listed.
+    ///
+    /// Parts which turn out not to be poll options are skipped.
+    pub fn get_options(&self, client: &Client) -> Result<Vec<PollOpt>> {
+        let mut options = Vec::with_capacity(self.parts.len());
+
+        for &part in &self.parts {
+            if let Some(option) = Item::get(client, part)?.poll_opt() {
+                options.push(option);
+            }
+        }
+
+        Ok(options)
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+
+    /// Whether this has been flagged or killed by moderators.
+    pub fn is_dead(&self) -> bool {
+        self.dead
+    }
+
+    pub fn by(&self) -> &str {
+        &self.by
+    }
+
+    pub fn kids(&self) -> &[i64] {
+        &self.kids
+    }
+
+    pub fn parts(&self) -> &[i64] {
+        &self.parts
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    pub fn descendants(&self) -> i64 {
+        self.descendants
+    }
+
+    pub fn url(&self) -> String {
+        format!("https://news.ycombinator.com/item?id={}", self.id())
+    }
+}
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use chrono::serde::ts_seconds;
+
+use super::Result;
+
+use super::client::Client;
+use super::item::Item;
+use super::poll::Poll;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PollOpt {
+    id: i64,
+
+    #[serde(default)]
+    by: String,
+
+    /// The poll this is an option of.
+    poll: i64,
+
+    #[serde(default)]
+    text: String,
+
+    #[serde(default)]
+    deleted: bool,
+
+    #[serde(default)]
+    dead: bool,
+
+    #[serde(with = "ts_seconds")]
+    time: DateTime<Utc>,
+
+    #[serde(default)]
+    score: i64,
+}
+
+impl PollOpt {
+    /// Fetch the poll this is an option of.
+    pub fn get_poll(&self, client: &Client) -> Result<Option<Poll>> {
+        client.get_item_immutable(self.poll).map(Item::poll)
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+
+    /// Whether this has been flagged or killed by moderators.
+    pub fn is_dead(&self) -> bool {
+        self.dead
+    }
+
+    pub fn by(&self) -> &str {
+        &self.by
+    }
+
+    pub fn poll(&self) -> i64 {
+        self.poll
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+
+    /// The number of votes this option received.
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    pub fn url(&self) -> String {
+        format!("https://news.ycombinator.com/item?id={}", self.id())
+    }
+}
@@ -1,142 +1,561 @@
-use super::{ErrorKind, Result};
+use std::cmp;
+use std::sync::Arc;
+
+use chrono::Duration;
 
-use serenity::model::{Channel, Message};
-use serenity::utils::MessageBuilder;
+use super::{ErrorKind, Result};
 
 use url::Url;
 
 use slog::Logger;
 
-use preview::Preview;
+use errors;
+use preview::{Preview, Card, Block};
+use util::html_to_markdown::convert_logged;
+use util::plural;
+use util::truncate::truncate;
 
+use super::client::Client;
 use super::item::Item;
+use super::link::{self, Target};
 use super::story::Story;
 use super::comment::Comment;
+use super::job::Job;
+use super::poll::Poll;
+use super::pollopt::PollOpt;
+use super::thread::Walker;
+use super::user::User;
 
-pub fn channel_from_message(message: &Message) -> Result<Channel> {
-    match message.channel_id.get() {
-        Ok(channel) => Ok(channel),
-        Err(e) => bail!(e),
-    }
+/// Describe a duration in its largest whole unit, e.g. "**3** years".
+fn describe_age(age: Duration) -> String {
+    let days = age.num_days();
+
+    let (count, unit) = if days >= 365 {
+        (days / 365, "year")
+    } else if days >= 30 {
+        (days / 30, "month")
+    } else {
+        (days, "day")
+    };
+
+    plural(count, unit)
 }
 
-pub struct HackerNews;
+/// The most characters of a comment's body shown in a comment preview.
+pub const COMMENT_LENGTH: usize = 1800;
+
+/// The most characters of a parent comment shown as context for a reply.
+pub const PARENT_CONTEXT_LENGTH: usize = 300;
+
+/// The most characters of each reply shown under a comment.
+pub const REPLY_LENGTH: usize = 300;
+
+/// The most characters of a self-post's text shown in a story preview.
+pub const STORY_TEXT_LENGTH: usize = 1000;
+
+pub struct HackerNews {
+    client: Arc<Client>,
+    walker: Walker,
+    parent_context: bool,
+    top_replies: usize,
+}
 
 impl HackerNews {
-    pub fn preview_story(&self, message: &Message, story: Story, log: &Logger) -> Result<()> {
-        let channel = channel_from_message(message)?;
+    pub fn new(client: Arc<Client>) -> HackerNews {
+        HackerNews {
+            walker: Walker::new(client.clone()),
+            client,
+            parent_context: false,
+            top_replies: 0,
+        }
+    }
 
-        let description = format!(
-            "**{}** points. **{}** comments",
-            story.score(),
-            story.kids().len()
-        );
+    /// Whether comment previews should include the comment being replied to,
+    /// if any. This is off by default.
+    pub fn parent_context(mut self, enabled: bool) -> HackerNews {
+        self.parent_context = enabled;
+        self
+    }
 
-        // TODO
-        // Also send story body in the case of self-posts, as with comment?
-
-        channel
-            .send_message(|m| {
-                m.embed(|e| {
-                    e.url(&story.url())
-                        .title(story.title())
-                        .description(&description)
-                        .color(super::ORANGE)
-                        .timestamp(&story.local_time())
-                        .footer(|f| f.icon_url(super::THUMBNAIL).text("Hacker News"))
-                })
-            })
-            .map(|message| {
-                info!(log, "Sent message"; "id" => message.id.0);
-            })
-            .map_err(From::from)
+    /// How many of the top replies to a comment to show under its preview,
+    /// which are fetched concurrently. This is 0 by default.
+    pub fn top_replies(mut self, count: usize) -> HackerNews {
+        self.top_replies = count;
+        self
+    }
+
+    fn card(&self) -> Card {
+        Card::new()
+            .color(super::ORANGE)
+            .footer("Hacker News", Some(super::THUMBNAIL))
     }
 
-    pub fn preview_comment(&self, message: &Message, comment: Comment, log: &Logger) -> Result<()> {
-        let channel = channel_from_message(message)?;
+    /// Preview an item which has been deleted or killed, which is missing
+    /// most of its content.
+    pub fn preview_removed(&self, item: &Item, _log: &Logger) -> Card {
+        let (title, description) = if item.is_deleted() {
+            ("[deleted]", "This item has been deleted.")
+        } else {
+            ("[flagged]", "This item has been flagged.")
+        };
+
+        self.card()
+            .url(item.url())
+            .title(title)
+            .description(description)
+    }
+
+    /// Preview any kind of HN URL.
+    pub fn preview_url(&self, url: &Url, log: &Logger) -> Result<Card> {
+        match link::recognize(url) {
+            Some(Target::Item(id)) => self.preview_item(Item::get(&*self.client, id)?, log),
+            Some(Target::User(id)) => self.preview_user(User::get(&*self.client, &id)?, log),
+            None => bail!(ErrorKind::UnrecognizedUrl),
+        }
+    }
+
+    pub fn preview_item(&self, item: Item, log: &Logger) -> Result<Card> {
+        match item {
+            ref item if item.is_deleted() || item.is_dead() => {
+                info!(log, "Item has been removed";
+                      "deleted" => item.is_deleted(), "dead" => item.is_dead());
+
+                Ok(self.preview_removed(item, log))
+            }
+            Item::Story(story) => self.preview_story(story, log),
+            Item::Comment(comment) => self.preview_comment(comment, log),
+            Item::Job(job) => self.preview_job(job, log),
+            Item::Poll(poll) => self.preview_poll(poll, log),
+            Item::PollOpt(poll_opt) => self.preview_poll_opt(poll_opt, log),
+        }
+    }
+
+    pub fn preview_story(&self, story: Story, log: &Logger) -> Result<Card> {
+        let mut description = format!(
+            "{}. {}",
+            plural(story.score(), "point"),
+            plural(story.kids().len() as i64, "comment")
+        );
+
+        // Link posts lead to the linked page, so make sure the discussion is
+        // still reachable.
+        let url = match story.url() {
+            Some(url) => {
+                description.push_str(&format!(". [Discussion]({})", story.item_url()));
+
+                url.to_string()
+            }
+            None => story.item_url(),
+        };
+
+        if !story.text().is_empty() {
+            description.push_str("\n\n");
+            description.push_str(&truncate(
+                &convert_logged(story.text(), log),
+                STORY_TEXT_LENGTH,
+                Some(&story.item_url()),
+            ));
+        }
+
+        Ok(self.card()
+            .url(url)
+            .title(story.title())
+            .description(description)
+            .timestamp(story.time()))
+    }
 
+    pub fn preview_comment(&self, comment: Comment, log: &Logger) -> Result<Card> {
         let log = log.new(o!("comment_id" => comment.id()));
 
         let description = if comment.kids().is_empty() {
-            MessageBuilder::new()
-                .push("by ")
-                .push_bold(comment.by())
-                .build()
+            format!("by **{}**", comment.by())
         } else {
-            MessageBuilder::new()
-                .push_bold(&comment.kids().len().to_string())
-                .push(" replies. by ")
-                .push_bold(comment.by())
-                .build()
+            format!("**{}** replies. by **{}**", comment.kids().len(), comment.by())
         };
 
-        let title = match comment.get_story() {
-            Ok(story) => format!("Comment on: {}", story.title()),
+        let title = match comment.get_root(&*self.client) {
+            Ok(ref root) if root.is_deleted() => String::from("Comment on: [deleted]"),
+            Ok(ref root) if root.is_dead() => String::from("Comment on: [flagged]"),
+            Ok(root) => format!("Comment on: {}", root.title().unwrap_or("[untitled]")),
             Err(e) => {
                 error!(log, "Couldn't get comment's story"; "error" => e.to_string());
                 bail!(e);
             }
         };
 
-        channel
-            .send_message(|m| {
-                m.embed(|e| {
-                    e.url(&comment.url())
-                        .title(&title)
-                        .description(&description)
-                        .color(super::ORANGE)
-                        .timestamp(&comment.local_time())
-                        .footer(|f| f.icon_url(super::THUMBNAIL).text("Hacker News"))
-                })
-            })
-            .map(|message| {
-                info!(log, "Sent HN comment embed"; "id" => message.id.0);
-            })?;
-
-        let body = comment.text_as_markdown()?;
-
-        let comment = MessageBuilder::new()
-            .push(":speech_left: ")
-            .push_bold("BEGIN QUOTE")
-            .push(" :speech_balloon:\n")
-            .push(&body)
-            .push("\n")
-            .push(":speech_left: ")
-            .push_bold("END QUOTE")
-            .push(" :speech_balloon:")
-            .build();
-
-        channel
-            .say(&comment)
-            .map(|message| {
-                info!(log, "Sent HN comment body"; "id" => message.id.0);
-            })
-            .map_err(From::from)
-    }
-}
+        let body = truncate(
+            &convert_logged(comment.text(), &log),
+            COMMENT_LENGTH,
+            Some(&comment.url()),
+        );
 
-impl Preview for HackerNews {
-    fn preview(&self, url: &Url, message: &Message, log: &Logger) {
-        let result = Item::from_url(&url).and_then(|item| match item {
-            Item::Story(story) => self.preview_story(message, story, log),
-            Item::Comment(comment) => self.preview_comment(message, comment, log),
-        });
-
-        match result {
-            Ok(()) => {
-                info!(log, "Previewed HN URL");
+        let mut card = self.card()
+            .url(comment.url())
+            .title(title)
+            .description(description)
+            .timestamp(comment.time());
+
+        if self.parent_context {
+            if let Some(context) = self.parent_context_for(&comment, &log) {
+                card = card.block(Block::Text(context));
             }
+        }
+
+        card = card.block(Block::Quote(body));
+
+        for reply in self.replies_to(&comment, &log) {
+            card = card.block(Block::Text(reply));
+        }
+
+        Ok(card)
+    }
+
+    /// Describe the top replies to the given comment, skipping any that have
+    /// been removed.
+    ///
+    /// The replies are only a nicety, so failing to fetch them is logged
+    /// rather than failing the whole preview.
+    fn replies_to(&self, comment: &Comment, log: &Logger) -> Vec<String> {
+        let count = cmp::min(self.top_replies, comment.kids().len());
+
+        if count == 0 {
+            return vec![];
+        }
+
+        let nodes = match self.walker.items(&comment.kids()[..count], 1) {
+            Ok(nodes) => nodes,
             Err(e) => {
-                match *e.kind() {
-                    ErrorKind::InvalidHost => {
-                        info!(log, "Ignoring non-HN URL");
-                    }
-                    _ => {
-                        error!(log, "Couldn't preview HN URL"; "error" => e.to_string());
-                    }
+                warn!(log, "Couldn't get comment's replies"; "error" => e.to_string());
+                return vec![];
+            }
+        };
+
+        nodes
+            .iter()
+            .filter_map(|node| match *node.item() {
+                Item::Comment(ref reply) if !reply.is_deleted() && !reply.is_dead() => {
+                    Some(format!(
+                        "Reply by **{}**: {}",
+                        reply.by(),
+                        truncate(&convert_logged(reply.text(), log), REPLY_LENGTH, None)
+                    ))
                 }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Describe the comment that the given comment is replying to.
+    ///
+    /// Top-level comments have no parent comment, in which case the root
+    /// story's title in the card is context enough. So is no context at all
+    /// if the parent can't be fetched.
+    fn parent_context_for(&self, comment: &Comment, log: &Logger) -> Option<String> {
+        let parent = match self.client.get_item_immutable(comment.parent()) {
+            Ok(Item::Comment(parent)) => parent,
+            Ok(_) => return None,
+            Err(e) => {
+                warn!(log, "Couldn't get comment's parent"; "error" => e.to_string());
+                return None;
             }
+        };
+
+        let context = if parent.is_deleted() {
+            String::from("In reply to a deleted comment")
+        } else if parent.is_dead() {
+            String::from("In reply to a flagged comment")
+        } else {
+            format!(
+                "In reply to **{}**: {}",
+                parent.by(),
+                truncate(&convert_logged(parent.text(), log), PARENT_CONTEXT_LENGTH, None)
+            )
+        };
+
+        Some(context)
+    }
+
+    pub fn preview_user(&self, user: User, log: &Logger) -> Result<Card> {
+        let mut description = format!(
+            "**{}** karma. {} in total. Joined {} ago.",
+            user.karma(),
+            plural(user.submitted().len() as i64, "submission"),
+            describe_age(user.age())
+        );
+
+        if !user.about().is_empty() {
+            description.push_str("\n\n");
+            description.push_str(&convert_logged(user.about(), log));
         }
+
+        Ok(self.card()
+            .url(user.url())
+            .title(user.id())
+            .description(description)
+            .timestamp(user.created()))
+    }
+
+    pub fn preview_job(&self, job: Job, log: &Logger) -> Result<Card> {
+        let mut card = self.card()
+            .url(job.url().map(String::from).unwrap_or_else(|| job.item_url()))
+            .title(job.title())
+            .description(format!("Job posting by **{}**", job.by()))
+            .timestamp(job.time());
+
+        if !job.text().is_empty() {
+            card = card.block(Block::Quote(convert_logged(job.text(), log)));
+        }
+
+        Ok(card)
+    }
+
+    pub fn preview_poll(&self, poll: Poll, log: &Logger) -> Result<Card> {
+        let log = log.new(o!("poll_id" => poll.id()));
+
+        let options = poll.get_options(&*self.client)?;
+
+        info!(log, "Fetched poll options"; "count" => options.len());
+
+        let mut description = format!(
+            "{}. {}",
+            plural(poll.score(), "point"),
+            plural(poll.descendants(), "comment")
+        );
+
+        for option in &options {
+            description.push_str(&format!(
+                "\n{}: {}",
+                plural(option.score(), "vote"),
+                convert_logged(option.text(), &log)
+            ));
+        }
+
+        Ok(self.card()
+            .url(poll.url())
+            .title(poll.title())
+            .description(description)
+            .timestamp(poll.time()))
+    }
+
+    pub fn preview_poll_opt(&self, poll_opt: PollOpt, log: &Logger) -> Result<Card> {
+        let title = match poll_opt.get_poll(&*self.client)? {
+            Some(poll) => format!("Option on: {}", poll.title()),
+            None => String::from("Poll option"),
+        };
+
+        Ok(self.card()
+            .url(poll_opt.url())
+            .title(title)
+            .description(format!(
+                "{}: {}",
+                plural(poll_opt.score(), "vote"),
+                convert_logged(poll_opt.text(), log)
+            ))
+            .timestamp(poll_opt.time()))
+    }
+}
+
+impl Preview for HackerNews {
+    fn name(&self) -> &'static str {
+        "Hacker News"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        link::recognize(url).is_some()
+    }
+
+    fn preview(&self, url: &Url, log: &Logger) -> errors::Result<Option<Card>> {
+        let result = self.preview_url(url, log);
+
+        if let Some(stats) = self.client.cache_stats() {
+            info!(log, "HN cache stats"; "hits" => stats.hits(), "misses" => stats.misses());
+        }
+
+        result.map(Some).map_err(From::from)
     }
 }
 
+#[test]
+fn test_preview_comment() {
+    use slog::Discard;
+
+    use super::client;
+
+    let log = Logger::root(Discard, o!());
+    let hn = HackerNews::new(Arc::new(client::fixtures()));
+
+    let url = Url::parse("https://news.ycombinator.com/item?id=14775602").unwrap();
+
+    assert!(hn.matches(&url));
+
+    let card = hn.preview(&url, &log)
+        .expect("Couldn't preview comment")
+        .expect("Comment had no preview");
+
+    assert_eq!(
+        Some("Comment on: The Mercurial Coding Style"),
+        card.get_title()
+    );
+    assert_eq!(
+        Some("https://news.ycombinator.com/item?id=14775602"),
+        card.get_url()
+    );
+    assert_eq!(Some("by **kentonv**"), card.get_description());
+    assert_eq!(
+        &[Block::Quote(String::from("Same here. Consistency beats cleverness."))],
+        card.body()
+    );
+}
+
+#[test]
+fn test_preview_parent_context() {
+    use slog::Discard;
+
+    use super::client;
+
+    let log = Logger::root(Discard, o!());
+    let hn = HackerNews::new(Arc::new(client::fixtures())).parent_context(true);
+
+    let url = Url::parse("https://news.ycombinator.com/item?id=14775602").unwrap();
+
+    let card = hn.preview(&url, &log)
+        .expect("Couldn't preview comment")
+        .expect("Comment had no preview");
+
+    assert_eq!(
+        &[
+            Block::Text(String::from(
+                "In reply to **jlarocco**: I don't agree with all of it, but I'd rather \
+                 work in a code base with a style guide I dislike than one with no style \
+                 guide at all.",
+            )),
+            Block::Quote(String::from("Same here. Consistency beats cleverness.")),
+        ],
+        card.body()
+    );
+}
+
+#[test]
+fn test_preview_top_replies() {
+    use slog::Discard;
+
+    use super::client;
+
+    let log = Logger::root(Discard, o!());
+    let hn = HackerNews::new(Arc::new(client::fixtures())).top_replies(3);
+
+    let url = Url::parse("https://news.ycombinator.com/item?id=14775347").unwrap();
+
+    let card = hn.preview(&url, &log)
+        .expect("Couldn't preview comment")
+        .expect("Comment had no preview");
+
+    assert_eq!(
+        Some(&Block::Text(String::from(
+            "Reply by **kentonv**: Same here. Consistency beats cleverness.",
+        ))),
+        card.body().last()
+    );
+}
+
+#[test]
+fn test_preview_story() {
+    use slog::Discard;
+
+    use super::client;
+
+    let log = Logger::root(Discard, o!());
+    let hn = HackerNews::new(Arc::new(client::fixtures()));
+
+    let link = Url::parse("https://news.ycombinator.com/item?id=14774167").unwrap();
+
+    let card = hn.preview(&link, &log)
+        .expect("Couldn't preview story")
+        .expect("Story had no preview");
+
+    assert_eq!(
+        Some("https://www.mercurial-scm.org/wiki/CodingStyle"),
+        card.get_url()
+    );
+    assert_eq!(
+        Some(
+            "**42** points. **1** comment. \
+             [Discussion](https://news.ycombinator.com/item?id=14774167)"
+        ),
+        card.get_description()
+    );
+
+    let ask = Url::parse("https://news.ycombinator.com/item?id=121003").unwrap();
+
+    let card = hn.preview(&ask, &log)
+        .expect("Couldn't preview story")
+        .expect("Story had no preview");
+
+    assert_eq!(
+        Some("https://news.ycombinator.com/item?id=121003"),
+        card.get_url()
+    );
+    assert_eq!(
+        Some(
+            "**25** points. **2** comments\n\n\
+             Or does it just get in the way?\n\nI'm curious what people think."
+        ),
+        card.get_description()
+    );
+}
+
+#[test]
+fn test_preview_deleted() {
+    use slog::Discard;
+
+    use super::client;
+
+    let log = Logger::root(Discard, o!());
+    let hn = HackerNews::new(Arc::new(client::fixtures()));
+
+    let url = Url::parse("https://news.ycombinator.com/item?id=15023220").unwrap();
+
+    let card = hn.preview(&url, &log)
+        .expect("Couldn't preview deleted comment")
+        .expect("Deleted comment had no preview");
+
+    assert_eq!(Some("[deleted]"), card.get_title());
+    assert!(card.body().is_empty());
+}
+
+#[test]
+fn test_preview_poll() {
+    use slog::Discard;
+
+    use super::client;
+
+    let log = Logger::root(Discard, o!());
+    let hn = HackerNews::new(Arc::new(client::fixtures()));
+
+    let url = Url::parse("https://news.ycombinator.com/item?id=126809").unwrap();
+
+    let card = hn.preview(&url, &log)
+        .expect("Couldn't preview poll")
+        .expect("Poll had no preview");
+
+    assert_eq!(
+        Some("Poll: What would happen if News.YC had explicit support for polls?"),
+        card.get_title()
+    );
+    assert_eq!(
+        Some(
+            "**46** points. **54** comments\n\
+             **335** votes: Yes, I'd vote in them.\n\
+             **163** votes: No, they'd be a waste of space.\n\
+             **73** votes: Only if they were restricted to Ask/Show HN."
+        ),
+        card.get_description()
+    );
+}
+
+#[test]
+fn test_describe_age() {
+    assert_eq!("**1** day", describe_age(Duration::days(1)));
+    assert_eq!("**2** months", describe_age(Duration::days(65)));
+    assert_eq!("**11** years", describe_age(Duration::days(11 * 365 + 40)));
+}
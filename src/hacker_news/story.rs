@@ -2,13 +2,13 @@ use chrono::{DateTime, Utc, Local};
 use chrono::serde::ts_seconds;
 use chrono_tz::Tz;
 
-use util;
-
 use super::{Error, ErrorKind, Result};
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct Story {
     id: i64,
+
+    #[serde(default)]
     by: String,
 
     #[serde(default)]
@@ -17,12 +17,25 @@ pub struct Story {
     #[serde(default)]
     text: String,
 
+    #[serde(default)]
+    deleted: bool,
+
+    #[serde(default)]
+    dead: bool,
+
     #[serde(with = "ts_seconds")]
     time: DateTime<Utc>,
 
+    #[serde(default)]
     title: String,
+
+    #[serde(default)]
     url: String,
+
+    #[serde(default)]
     score: i64,
+
+    #[serde(default)]
     descendants: i64,
 }
 
@@ -31,6 +44,15 @@ impl Story {
         self.id
     }
 
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+
+    /// Whether this has been flagged or killed by moderators.
+    pub fn is_dead(&self) -> bool {
+        self.dead
+    }
+
     pub fn by(&self) -> &str {
         &self.by
     }
@@ -43,10 +65,6 @@ impl Story {
         &self.text
     }
 
-    pub fn text_as_markdown(&self) -> Result<String> {
-        util::html_to_markdown::convert(&self.text).map_err(From::from)
-    }
-
     pub fn time(&self) -> DateTime<Utc> {
         self.time
     }
@@ -69,7 +87,17 @@ impl Story {
         &self.title
     }
 
-    pub fn url(&self) -> String {
+    /// The URL the story links to. Self-posts, such as Ask HN, have none.
+    pub fn url(&self) -> Option<&str> {
+        if self.url.is_empty() {
+            None
+        } else {
+            Some(&self.url)
+        }
+    }
+
+    /// The URL of the story's page on Hacker News.
+    pub fn item_url(&self) -> String {
         format!("https://news.ycombinator.com/item?id={}", self.id())
     }
 
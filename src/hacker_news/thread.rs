@@ -0,0 +1,245 @@
+use std::cmp;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use super::{ErrorKind, Result};
+
+use super::client::Client;
+use super::comment::Comment;
+use super::item::Item;
+
+/// The deepest a comment's parent chain is walked before giving up.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// The number of requests made concurrently when fetching replies.
+pub const DEFAULT_WORKERS: usize = 8;
+
+/// Fetch a comment's ancestors, from its immediate parent up to the root
+/// story, poll or job.
+///
+/// Fails with `ErrorKind::ThreadTooDeep` if the root isn't reached within
+/// `max_depth` items.
+pub fn ancestors(client: &Client, comment: &Comment, max_depth: usize) -> Result<Vec<Item>> {
+    let mut ancestors = vec![];
+    let mut parent = comment.parent();
+
+    loop {
+        ensure!(ancestors.len() < max_depth, ErrorKind::ThreadTooDeep(max_depth));
+
+        let item = client.get_item_immutable(parent)?;
+
+        let next = match item {
+            Item::Comment(ref comment) => Some(comment.parent()),
+            _ => None,
+        };
+
+        ancestors.push(item);
+
+        match next {
+            Some(next) => parent = next,
+            None => return Ok(ancestors),
+        }
+    }
+}
+
+/// An item along with the replies that were fetched for it.
+#[derive(Clone, Debug)]
+pub struct Node {
+    item: Item,
+    children: Vec<Node>,
+}
+
+impl Node {
+    pub fn item(&self) -> &Item {
+        &self.item
+    }
+
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+}
+
+/// Walks comment threads in either direction.
+///
+/// Ancestors are necessarily fetched one at a time, since each item is needed
+/// to know its parent. Replies are fetched a level at a time, with all of the
+/// items in a level fetched concurrently by a pool of worker threads.
+pub struct Walker {
+    client: Arc<Client>,
+    max_depth: usize,
+    workers: usize,
+}
+
+impl Walker {
+    pub fn new(client: Arc<Client>) -> Walker {
+        Walker {
+            client,
+            max_depth: DEFAULT_MAX_DEPTH,
+            workers: DEFAULT_WORKERS,
+        }
+    }
+
+    /// Set the deepest a parent chain is walked before giving up.
+    pub fn max_depth(mut self, max_depth: usize) -> Walker {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Set the number of requests made concurrently when fetching replies.
+    pub fn workers(mut self, workers: usize) -> Walker {
+        self.workers = cmp::max(workers, 1);
+        self
+    }
+
+    /// See `ancestors`.
+    pub fn ancestors(&self, comment: &Comment) -> Result<Vec<Item>> {
+        ancestors(&*self.client, comment, self.max_depth)
+    }
+
+    /// Fetch the replies to an item, up to `depth` levels deep.
+    ///
+    /// Replies are in the same order as the item's kids, which is the order
+    /// in which they're ranked on the site.
+    pub fn replies(&self, item: &Item, depth: usize) -> Result<Vec<Node>> {
+        self.fetch_level(item.kids(), depth)
+    }
+
+    /// Fetch the given items, along with their replies up to `depth` levels
+    /// deep in all, so a `depth` of 1 fetches only the items themselves.
+    pub fn items(&self, ids: &[i64], depth: usize) -> Result<Vec<Node>> {
+        self.fetch_level(ids, depth)
+    }
+
+    fn fetch_level(&self, ids: &[i64], depth: usize) -> Result<Vec<Node>> {
+        if depth == 0 || ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let items = self.fetch_all(ids)?;
+
+        // Fetch the whole next level at once, then hand each item its share
+        // of it, which works because the level is in the same order as the
+        // kids of the items in this level.
+        let next_ids = items
+            .iter()
+            .flat_map(|item| item.kids().iter().cloned())
+            .collect::<Vec<_>>();
+
+        let mut next_level = self.fetch_level(&next_ids, depth - 1)?.into_iter();
+
+        let nodes = items
+            .into_iter()
+            .map(|item| {
+                let count = item.kids().len();
+
+                Node {
+                    item,
+                    children: next_level.by_ref().take(count).collect(),
+                }
+            })
+            .collect();
+
+        Ok(nodes)
+    }
+
+    /// Fetch all of the given items concurrently, preserving their order.
+    fn fetch_all(&self, ids: &[i64]) -> Result<Vec<Item>> {
+        let (job_tx, job_rx) = mpsc::channel();
+        let job_rx = Arc::new(Mutex::new(job_rx));
+
+        for job in ids.iter().cloned().enumerate() {
+            job_tx.send(job).expect("HN fetch queue was closed");
+        }
+
+        drop(job_tx);
+
+        let (result_tx, result_rx) = mpsc::channel();
+
+        // Set once any item fails, since the rest are then moot, so that the
+        // workers stop rather than fetching whatever's left in the queue.
+        let aborted = Arc::new(AtomicBool::new(false));
+
+        for _ in 0..cmp::min(self.workers, ids.len()) {
+            let client = self.client.clone();
+            let job_rx = job_rx.clone();
+            let result_tx = result_tx.clone();
+            let aborted = aborted.clone();
+
+            thread::spawn(move || loop {
+                if aborted.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let job = job_rx.lock().expect("HN fetch queue lock was poisoned").recv();
+
+                match job {
+                    Ok((index, id)) => {
+                        let result = client.get_item(id);
+
+                        if result.is_err() {
+                            aborted.store(true, Ordering::SeqCst);
+                        }
+
+                        // The receiver only hangs up early if another item
+                        // failed, in which case this result is moot anyway.
+                        let _ = result_tx.send((index, result));
+                    }
+                    Err(_) => break,
+                }
+            });
+        }
+
+        drop(result_tx);
+
+        let mut items = vec![None; ids.len()];
+
+        for (index, result) in result_rx {
+            items[index] = Some(result?);
+        }
+
+        Ok(items
+            .into_iter()
+            .map(|item| item.expect("HN fetch worker dropped an item"))
+            .collect())
+    }
+}
+
+#[test]
+fn test_ancestors() {
+    use super::client;
+
+    let client = client::fixtures();
+
+    let comment = Item::get(&client, 14775602)
+        .expect("Couldn't get item")
+        .comment()
+        .expect("Not a comment!");
+
+    let ids = ancestors(&client, &comment, DEFAULT_MAX_DEPTH)
+        .expect("Couldn't get ancestors")
+        .iter()
+        .map(Item::id)
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec![14775347, 14774167], ids);
+
+    assert!(ancestors(&client, &comment, 1).is_err());
+}
+
+#[test]
+fn test_replies() {
+    use super::client;
+
+    let walker = Walker::new(Arc::new(client::fixtures())).workers(2);
+
+    let story = Item::get(&*walker.client, 14774167).expect("Couldn't get item");
+    let replies = walker.replies(&story, 2).expect("Couldn't get replies");
+
+    assert_eq!(1, replies.len());
+    assert_eq!(14775347, replies[0].item().id());
+    assert_eq!(1, replies[0].children().len());
+    assert_eq!(14775602, replies[0].children()[0].item().id());
+    assert!(replies[0].children()[0].children().is_empty());
+}
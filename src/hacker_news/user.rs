@@ -0,0 +1,72 @@
+use chrono::{DateTime, Duration, Utc};
+use chrono::serde::ts_seconds;
+
+use super::Result;
+
+use super::client::Client;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct User {
+    id: String,
+
+    #[serde(with = "ts_seconds")]
+    created: DateTime<Utc>,
+
+    karma: i64,
+
+    #[serde(default)]
+    about: String,
+
+    #[serde(default)]
+    submitted: Vec<i64>,
+}
+
+impl User {
+    pub fn get(client: &Client, id: &str) -> Result<User> {
+        client.get_user(id)
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn created(&self) -> DateTime<Utc> {
+        self.created
+    }
+
+    /// How long ago this account was created.
+    pub fn age(&self) -> Duration {
+        Utc::now().signed_duration_since(self.created)
+    }
+
+    pub fn karma(&self) -> i64 {
+        self.karma
+    }
+
+    pub fn about(&self) -> &str {
+        &self.about
+    }
+
+    /// The ids of this user's stories, polls and comments, newest first.
+    pub fn submitted(&self) -> &[i64] {
+        &self.submitted
+    }
+
+    pub fn url(&self) -> String {
+        format!("https://news.ycombinator.com/user?id={}", self.id())
+    }
+}
+
+#[test]
+fn test_user_parse() {
+    use super::client;
+
+    let client = client::fixtures();
+
+    let user = User::get(&client, "pg").expect("Couldn't get user");
+
+    assert_eq!("pg", user.id());
+    assert_eq!(155111, user.karma());
+    assert_eq!(5, user.submitted().len());
+    assert_eq!("https://news.ycombinator.com/user?id=pg", user.url());
+}
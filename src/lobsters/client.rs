@@ -0,0 +1,54 @@
+use util::http::{self, Limits};
+
+use super::Result;
+
+use super::story::{Story, Comment};
+
+pub const BASE_URL: &'static str = "https://lobste.rs";
+
+/// Talks to the Lobsters JSON API.
+pub struct Client {
+    base_url: String,
+    limits: Limits,
+}
+
+impl Client {
+    pub fn new() -> Client {
+        Client::with_base_url(BASE_URL)
+    }
+
+    /// Create a client which talks to a site rooted at `base_url` instead of
+    /// Lobsters.
+    pub fn with_base_url<S: Into<String>>(base_url: S) -> Client {
+        Client {
+            base_url: base_url.into().trim_right_matches('/').to_string(),
+            // Stories include all of their comments.
+            limits: Limits {
+                max_size: 2 * 1024 * 1024,
+                ..Limits::default()
+            },
+        }
+    }
+
+    pub fn get_story(&self, short_id: &str) -> Result<Story> {
+        let url = format!("{}/s/{}.json", self.base_url, short_id);
+
+        Ok(http::get_json(&url, self.limits)?)
+    }
+
+    /// Get a comment on its own, which says which story it's on but not
+    /// what the story is.
+    pub fn get_comment(&self, short_id: &str) -> Result<Comment> {
+        let url = format!("{}/c/{}.json", self.base_url, short_id);
+
+        Ok(http::get_json(&url, self.limits)?)
+    }
+}
+
+/// A `Client` talking to a server for the fixtures in `tests/fixtures/lobsters`.
+#[cfg(test)]
+pub fn fixtures() -> Client {
+    use util::fixture_server::FixtureServer;
+
+    Client::with_base_url(FixtureServer::start("lobsters").url(""))
+}
@@ -0,0 +1,83 @@
+use url::Url;
+
+/// The story or comment on Lobsters that a URL refers to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    Story(String),
+    Comment { story: String, id: String },
+
+    /// A `/c/<id>` comment permalink, which doesn't say which story the
+    /// comment belongs to.
+    Permalink(String),
+}
+
+/// Figure out which story or comment a URL refers to, if any.
+///
+/// Comments are recognized both as fragments of their story's URL, e.g.
+/// `/s/d9dr4w/slug#c_gqjwyu`, and as `/c/gqjwyu` permalinks.
+pub fn recognize(url: &Url) -> Option<Target> {
+    match url.host_str() {
+        Some("lobste.rs") | Some("www.lobste.rs") => {}
+        _ => return None,
+    }
+
+    let segments = url.path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect::<Vec<_>>())
+        .unwrap_or_else(Vec::new);
+
+    if segments.len() < 2 || (segments[0] != "s" && segments[0] != "c") {
+        return None;
+    }
+
+    let short_id = segments[1].trim_right_matches(".json");
+
+    if short_id.is_empty() || !short_id.chars().all(char::is_alphanumeric) {
+        return None;
+    }
+
+    if segments[0] == "c" {
+        return Some(Target::Permalink(short_id.to_string()));
+    }
+
+    let story = short_id;
+
+    match url.fragment() {
+        Some(fragment) if fragment.starts_with("c_") && fragment.len() > 2 => {
+            Some(Target::Comment {
+                story: story.to_string(),
+                id: fragment[2..].to_string(),
+            })
+        }
+        _ => Some(Target::Story(story.to_string())),
+    }
+}
+
+#[test]
+fn test_recognize() {
+    let story = Some(Target::Story(String::from("d9dr4w")));
+
+    let cases = vec![
+        ("https://lobste.rs/s/d9dr4w/announcing_rust_1_20", story.clone()),
+        ("https://lobste.rs/s/d9dr4w", story.clone()),
+        ("https://lobste.rs/s/d9dr4w.json", story.clone()),
+        ("https://lobste.rs/s/d9dr4w/announcing_rust_1_20#comments", story.clone()),
+        ("https://lobste.rs/s/d9dr4w/announcing_rust_1_20#c_gqjwyu",
+         Some(Target::Comment {
+             story: String::from("d9dr4w"),
+             id: String::from("gqjwyu"),
+         })),
+        ("https://lobste.rs/", None),
+        ("https://lobste.rs/t/rust", None),
+        ("https://lobste.rs/c/gqjwyu", Some(Target::Permalink(String::from("gqjwyu")))),
+        ("https://lobste.rs/c/gqjwyu.json", Some(Target::Permalink(String::from("gqjwyu")))),
+        ("https://lobste.rs/c/", None),
+        ("https://lobste.rs/c/gq-jwyu", None),
+        ("https://example.com/s/d9dr4w", None),
+    ];
+
+    for (url, expected) in cases {
+        let parsed = Url::parse(url).expect("Couldn't parse test URL");
+
+        assert_eq!(expected, recognize(&parsed), "recognizing {}", url);
+    }
+}
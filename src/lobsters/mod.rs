@@ -0,0 +1,33 @@
+use util::http;
+
+mod client;
+mod story;
+mod link;
+mod previewer;
+
+pub use self::client::Client;
+pub use self::link::{Target, recognize};
+pub use self::previewer::Lobsters;
+
+pub const THUMBNAIL: &'static str = "https://lobste.rs/apple-touch-icon-144.png";
+pub const RED: u64 = 0xAC130D;
+
+error_chain! {
+    links {
+        Http(http::Error, http::ErrorKind);
+    }
+
+    errors {
+        UnrecognizedUrl {
+            description("URL doesn't refer to a Lobsters story or comment")
+        }
+        CommentNotFound(id: String) {
+            description("The comment isn't in the story")
+            display("Comment {} isn't in the story", id)
+        }
+        StoryNotFound(id: String) {
+            description("Couldn't tell which story the comment is on")
+            display("Couldn't tell which story comment {} is on", id)
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use url::Url;
+
+use slog::Logger;
+
+use errors;
+use preview::{Preview, Card, Block};
+use util::html_to_markdown::{convert_logged, escape};
+use util::plural;
+use util::truncate::truncate;
+
+use super::{ErrorKind, Result};
+
+use super::client::Client;
+use super::link::{self, Target};
+use super::story::{Story, Comment};
+
+/// The most characters of a comment's body shown in a comment preview.
+pub const COMMENT_LENGTH: usize = 1800;
+
+/// The most characters of a text story's text shown in a story preview.
+pub const STORY_TEXT_LENGTH: usize = 1000;
+
+pub struct Lobsters {
+    client: Client,
+}
+
+impl Lobsters {
+    pub fn new(client: Client) -> Lobsters {
+        Lobsters { client }
+    }
+
+    fn card(&self) -> Card {
+        Card::new()
+            .color(super::RED)
+            .footer("Lobsters", Some(super::THUMBNAIL))
+    }
+
+    pub fn preview_url(&self, url: &Url, log: &Logger) -> Result<Card> {
+        let target = link::recognize(url).ok_or(ErrorKind::UnrecognizedUrl)?;
+
+        info!(log, "Recognized Lobsters URL"; "target" => format!("{:?}", target));
+
+        match target {
+            Target::Story(short_id) => {
+                let story = self.client.get_story(&short_id)?;
+
+                Ok(self.preview_story(&story, log))
+            }
+            Target::Comment { story, id } => {
+                let story = self.client.get_story(&story)?;
+                let comment = story
+                    .comment(&id)
+                    .ok_or_else(|| ErrorKind::CommentNotFound(id.clone()))?;
+
+                Ok(self.preview_comment(&story, comment, log))
+            }
+            Target::Permalink(id) => {
+                let comment = self.client.get_comment(&id)?;
+                let story = Url::parse(comment.discussion_url())
+                    .ok()
+                    .and_then(|url| link::recognize(&url))
+                    .and_then(|target| match target {
+                        Target::Comment { story, .. } => Some(story),
+                        _ => None,
+                    })
+                    .ok_or_else(|| ErrorKind::StoryNotFound(id.clone()))?;
+                let story = self.client.get_story(&story)?;
+
+                Ok(self.preview_comment(&story, &comment, log))
+            }
+        }
+    }
+
+    pub fn preview_story(&self, story: &Story, log: &Logger) -> Card {
+        let mut description = format!(
+            "{}. {}",
+            plural(story.score(), "point"),
+            plural(story.comment_count(), "comment")
+        );
+
+        // Link stories lead to the linked page, so make sure the discussion
+        // is still reachable.
+        let url = match story.url() {
+            Some(url) => {
+                description.push_str(&format!(". [Discussion]({})", story.comments_url()));
+
+                url
+            }
+            None => story.comments_url(),
+        };
+
+        if !story.tags().is_empty() {
+            let tags = story.tags()
+                .iter()
+                .map(|tag| format!("`{}`", tag))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            description.push_str(&format!("\nTags: {}", tags));
+        }
+
+        if !story.description().is_empty() {
+            description.push_str("\n\n");
+            description.push_str(&truncate(
+                &convert_logged(story.description(), log),
+                STORY_TEXT_LENGTH,
+                Some(story.comments_url()),
+            ));
+        }
+
+        self.card()
+            .url(url)
+            .title(story.title())
+            .description(description)
+            .timestamp(story.created_at())
+    }
+
+    pub fn preview_comment(&self, story: &Story, comment: &Comment, log: &Logger) -> Card {
+        let card = self.card()
+            .url(comment.url())
+            .title(format!("Comment on: {}", story.title()))
+            .timestamp(comment.created_at());
+
+        if comment.is_deleted() || comment.is_moderated() {
+            return card.description("[removed]");
+        }
+
+        let body = truncate(
+            &convert_logged(comment.text(), log),
+            COMMENT_LENGTH,
+            Some(comment.url()),
+        );
+
+        card.description(format!(
+            "by **{}**. {}.",
+            escape(comment.by().username()),
+            plural(comment.score(), "point")
+        )).block(Block::Quote(body))
+    }
+}
+
+impl Preview for Lobsters {
+    fn name(&self) -> &'static str {
+        "Lobsters"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        link::recognize(url).is_some()
+    }
+
+    fn preview(&self, url: &Url, log: &Logger) -> errors::Result<Option<Card>> {
+        self.preview_url(url, log).map(Some).map_err(From::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use preview::Block;
+    use util::preview_fixture::preview_fixture;
+
+    use super::super::client;
+    use super::Lobsters;
+
+    #[test]
+    fn test_preview_story() {
+        let lobsters = Lobsters::new(client::fixtures());
+
+        let card = preview_fixture(&lobsters, "https://lobste.rs/s/d9dr4w/announcing_rust_1_20");
+
+        assert_eq!(Some("Announcing Rust 1.20"), card.get_title());
+        assert_eq!(
+            Some("https://blog.rust-lang.org/2017/08/31/Rust-1.20.html"),
+            card.get_url()
+        );
+        assert_eq!(
+            Some(
+                "**24** points. **2** comments. \
+                 [Discussion](https://lobste.rs/s/d9dr4w/announcing_rust_1_20)\n\
+                 Tags: `rust`, `release`",
+            ),
+            card.get_description()
+        );
+    }
+
+    #[test]
+    fn test_preview_comment() {
+        let lobsters = Lobsters::new(client::fixtures());
+
+        let url = "https://lobste.rs/s/d9dr4w/announcing_rust_1_20#c_gqjwyu";
+        let card = preview_fixture(&lobsters, url);
+
+        assert_eq!(Some("Comment on: Announcing Rust 1.20"), card.get_title());
+        assert_eq!(Some("https://lobste.rs/c/gqjwyu"), card.get_url());
+        assert_eq!(Some("by **some\\_one**. **5** points."), card.get_description());
+        assert_eq!(
+            &[Block::Quote(String::from("Associated constants are *so* nice."))],
+            card.body()
+        );
+
+        let permalinked = preview_fixture(&lobsters, "https://lobste.rs/c/gqjwyu");
+
+        assert_eq!(card, permalinked);
+
+        let url = "https://lobste.rs/s/d9dr4w/announcing_rust_1_20#c_x2k9aa";
+        let removed = preview_fixture(&lobsters, url);
+
+        assert_eq!(Some("[removed]"), removed.get_description());
+        assert!(removed.body().is_empty());
+    }
+}
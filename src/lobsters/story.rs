@@ -0,0 +1,152 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct User {
+    username: String,
+}
+
+impl User {
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Story {
+    short_id: String,
+    title: String,
+
+    /// The linked URL, which is empty for text stories.
+    #[serde(default)]
+    url: String,
+
+    /// The story's text as HTML.
+    #[serde(default)]
+    description: String,
+
+    comments_url: String,
+
+    #[serde(default)]
+    score: i64,
+
+    #[serde(default)]
+    comment_count: u64,
+
+    #[serde(default)]
+    tags: Vec<String>,
+
+    submitter_user: User,
+
+    created_at: DateTime<Utc>,
+
+    #[serde(default)]
+    comments: Vec<Comment>,
+}
+
+impl Story {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The linked URL, unless this is a text story.
+    pub fn url(&self) -> Option<&str> {
+        if self.url.is_empty() { None } else { Some(&self.url) }
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The URL of the story's discussion on Lobsters.
+    pub fn comments_url(&self) -> &str {
+        &self.comments_url
+    }
+
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    pub fn comment_count(&self) -> u64 {
+        self.comment_count
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn submitter(&self) -> &User {
+        &self.submitter_user
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    /// Find one of the story's comments by its short ID.
+    pub fn comment(&self, short_id: &str) -> Option<&Comment> {
+        self.comments.iter().find(|comment| comment.short_id == short_id)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Comment {
+    short_id: String,
+    short_id_url: String,
+
+    /// The comment's URL within its story's discussion.
+    #[serde(rename = "url")]
+    discussion_url: String,
+
+    /// The comment's text as HTML.
+    comment: String,
+
+    #[serde(default)]
+    score: i64,
+
+    #[serde(default)]
+    is_deleted: bool,
+
+    #[serde(default)]
+    is_moderated: bool,
+
+    commenting_user: User,
+
+    created_at: DateTime<Utc>,
+}
+
+impl Comment {
+    pub fn url(&self) -> &str {
+        &self.short_id_url
+    }
+
+    /// The URL of the comment within its story's discussion, e.g.
+    /// `/s/d9dr4w/slug#c_gqjwyu`.
+    pub fn discussion_url(&self) -> &str {
+        &self.discussion_url
+    }
+
+    pub fn text(&self) -> &str {
+        &self.comment
+    }
+
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+
+    /// Whether the comment has been removed by moderators.
+    pub fn is_moderated(&self) -> bool {
+        self.is_moderated
+    }
+
+    pub fn by(&self) -> &User {
+        &self.commenting_user
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
@@ -9,6 +9,7 @@ extern crate linkify;
 
 #[macro_use]
 extern crate html5ever;
+extern crate xml;
 extern crate reqwest;
 
 #[macro_use]
@@ -33,6 +34,16 @@ extern crate error_chain;
 mod preview;
 mod bot;
 mod hacker_news;
+mod github;
+mod reddit;
+mod lobsters;
+mod stackexchange;
+mod crates;
+mod wikipedia;
+mod arxiv;
+mod oembed;
+mod playground;
+mod opengraph;
 mod util;
 
 mod errors {
@@ -44,11 +55,22 @@ mod errors {
 
         links {
             HackerNews(::hacker_news::Error, ::hacker_news::ErrorKind);
+            GitHub(::github::Error, ::github::ErrorKind);
+            Reddit(::reddit::Error, ::reddit::ErrorKind);
+            Lobsters(::lobsters::Error, ::lobsters::ErrorKind);
+            StackExchange(::stackexchange::Error, ::stackexchange::ErrorKind);
+            Crates(::crates::Error, ::crates::ErrorKind);
+            Wikipedia(::wikipedia::Error, ::wikipedia::ErrorKind);
+            Arxiv(::arxiv::Error, ::arxiv::ErrorKind);
+            OEmbed(::oembed::Error, ::oembed::ErrorKind);
+            Playground(::playground::Error, ::playground::ErrorKind);
+            OpenGraph(::opengraph::Error, ::opengraph::ErrorKind);
         }
     }
 }
 
 use std::env;
+use std::sync::Arc;
 
 use slog::Drain;
 
@@ -83,7 +105,63 @@ fn main() {
 
         let mut bot = Bot::new(root_logger.new(o!("scope" => "Bot")));
 
-        bot.push_previewer(hacker_news::HackerNews);
+        let hn_client = Arc::new(hacker_news::Cache::new(hacker_news::HttpClient::new()));
+
+        // Set HN_PARENT_CONTEXT=1 to show the parent of previewed HN comments.
+        let hn_parent_context = env::var("HN_PARENT_CONTEXT")
+            .map(|v| v == "1" || v == "true")
+            .unwrap_or(false);
+
+        // Set HN_TOP_REPLIES to show that many replies under HN comments.
+        let hn_top_replies = env::var("HN_TOP_REPLIES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+
+        bot.push_previewer(
+            hacker_news::HackerNews::new(hn_client)
+                .parent_context(hn_parent_context)
+                .top_replies(hn_top_replies),
+        );
+
+        bot.push_previewer(github::GitHub::new(github::Client::new()));
+        bot.push_previewer(reddit::Reddit::new(reddit::Client::new()));
+        bot.push_previewer(lobsters::Lobsters::new(lobsters::Client::new()));
+        bot.push_previewer(stackexchange::StackExchange::new(stackexchange::Client::new()));
+        bot.push_previewer(crates::Crates::new(crates::Client::new()));
+        bot.push_previewer(wikipedia::Wikipedia::new(wikipedia::Client::new()));
+
+        // Set ARXIV_MAX_AUTHORS to change how many authors of a paper are named.
+        let mut arxiv = arxiv::Arxiv::new(arxiv::Client::new());
+
+        if let Some(max_authors) = env::var("ARXIV_MAX_AUTHORS").ok().and_then(|v| v.parse().ok()) {
+            arxiv = arxiv.max_authors(max_authors);
+        }
+
+        bot.push_previewer(arxiv);
+
+        // Videos and tracks are left to Discord's own embed unless the link
+        // starts partway through. Set OEMBED_ALWAYS=1 to preview them anyway.
+        let oembed_always = env::var("OEMBED_ALWAYS")
+            .map(|v| v == "1" || v == "true")
+            .unwrap_or(false);
+
+        bot.push_previewer(
+            oembed::OEmbed::new(oembed::Client::new()).only_when_better(!oembed_always),
+        );
+
+        // Set PLAYGROUND_OUTPUT=1 to also run playground gists and show their
+        // output.
+        let playground_output = env::var("PLAYGROUND_OUTPUT")
+            .map(|v| v == "1" || v == "true")
+            .unwrap_or(false);
+
+        bot.push_previewer(
+            playground::Playground::new(playground::Client::new()).show_output(playground_output),
+        );
+
+        // Any other page is previewed from its OpenGraph metadata.
+        bot.push_previewer(opengraph::OpenGraph::new());
 
         let mut client = bot::new_client(&token, bot);
 
@@ -0,0 +1,54 @@
+use url::Url;
+
+use util::http::{self, Limits};
+
+use super::Result;
+
+use super::embed::Embed;
+use super::provider::Provider;
+
+/// Talks to each provider's oEmbed endpoint.
+pub struct Client {
+    base_url: Option<String>,
+    limits: Limits,
+}
+
+impl Client {
+    pub fn new() -> Client {
+        Client {
+            base_url: None,
+            limits: Limits::default(),
+        }
+    }
+
+    /// Create a client which sends every provider's requests to
+    /// `{base_url}/{provider id}` instead of its endpoint, e.g.
+    /// `http://localhost:8080/youtube`.
+    pub fn with_base_url<S: Into<String>>(base_url: S) -> Client {
+        Client {
+            base_url: Some(base_url.into().trim_right_matches('/').to_string()),
+            limits: Limits::default(),
+        }
+    }
+
+    /// Ask a provider about the media at `url`.
+    pub fn get_embed(&self, provider: &Provider, url: &str) -> Result<Embed> {
+        let endpoint = match self.base_url {
+            Some(ref base_url) => format!("{}/{}", base_url, provider.id),
+            None => provider.endpoint.to_string(),
+        };
+
+        let endpoint = Url::parse_with_params(&endpoint, &[("format", "json"), ("url", url)])?;
+
+        Ok(http::get_json(endpoint.as_str(), self.limits)?)
+    }
+}
+
+/// A `Client` talking to a server for the fixtures in `tests/fixtures/oembed`,
+/// where each provider's response is in `{provider id}.json`.
+#[cfg(test)]
+pub fn fixtures() -> Client {
+    use util::fixture_server::FixtureServer;
+
+    Client::with_base_url(FixtureServer::start("oembed").url("/"))
+}
@@ -0,0 +1,43 @@
+/// What a provider's oEmbed endpoint says about some media.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Embed {
+    #[serde(default)]
+    title: Option<String>,
+
+    /// The channel or user who published the media.
+    #[serde(default)]
+    author_name: Option<String>,
+
+    #[serde(default)]
+    author_url: Option<String>,
+
+    #[serde(default)]
+    thumbnail_url: Option<String>,
+
+    /// The length of the media in seconds, which only some providers give.
+    /// YouTube's endpoint never does, so YouTube previews go without.
+    #[serde(default)]
+    duration: Option<u64>,
+}
+
+impl Embed {
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_ref().map(String::as_str)
+    }
+
+    pub fn author_name(&self) -> Option<&str> {
+        self.author_name.as_ref().map(String::as_str)
+    }
+
+    pub fn author_url(&self) -> Option<&str> {
+        self.author_url.as_ref().map(String::as_str)
+    }
+
+    pub fn thumbnail_url(&self) -> Option<&str> {
+        self.thumbnail_url.as_ref().map(String::as_str)
+    }
+
+    pub fn duration(&self) -> Option<u64> {
+        self.duration
+    }
+}
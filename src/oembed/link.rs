@@ -0,0 +1,261 @@
+use url::Url;
+
+use super::provider::{Provider, YOUTUBE, VIMEO, SOUNDCLOUD};
+
+/// The media that a URL refers to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Target {
+    pub provider: &'static Provider,
+
+    /// The canonical URL of the media, to ask the provider about.
+    pub url: String,
+
+    /// How many seconds into the media the link starts playing.
+    pub start: Option<u64>,
+}
+
+/// Parse a timestamp the way providers write them, e.g. `83`, `83s`,
+/// `1m23s`, `1h2m3s` or `1:23`, into seconds.
+///
+/// Timestamps too large to count in seconds aren't timestamps at all.
+fn parse_timestamp(timestamp: &str) -> Option<u64> {
+    if timestamp.is_empty() {
+        return None;
+    }
+
+    if timestamp.contains(':') {
+        let mut seconds: u64 = 0;
+
+        for part in timestamp.split(':') {
+            let number = match part.parse::<u64>() {
+                Ok(number) => number,
+                Err(_) => return None,
+            };
+
+            seconds = match seconds.checked_mul(60).and_then(|s| s.checked_add(number)) {
+                Some(seconds) => seconds,
+                None => return None,
+            };
+        }
+
+        return Some(seconds);
+    }
+
+    let mut seconds: u64 = 0;
+    let mut number: Option<u64> = None;
+
+    for c in timestamp.chars() {
+        let multiplier = match c {
+            '0'...'9' => {
+                let digit = c as u64 - '0' as u64;
+                number = number.unwrap_or(0).checked_mul(10).and_then(|n| n.checked_add(digit));
+
+                if number.is_none() {
+                    return None;
+                }
+
+                continue;
+            }
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+
+        let total = number
+            .take()
+            .and_then(|number| number.checked_mul(multiplier))
+            .and_then(|part| seconds.checked_add(part));
+
+        seconds = match total {
+            Some(total) => total,
+            None => return None,
+        };
+    }
+
+    seconds.checked_add(number.unwrap_or(0))
+}
+
+/// Where the link starts playing, from a `t` or `start` parameter or a `t`
+/// fragment, e.g. `#t=1m23s`.
+fn start(url: &Url) -> Option<u64> {
+    let timestamp = url.query_pairs()
+        .find(|param| param.0 == "t" || param.0 == "start")
+        .map(|param| param.1.into_owned())
+        .or_else(|| {
+            url.fragment()
+                .and_then(|fragment| if fragment.starts_with("t=") {
+                    Some(fragment["t=".len()..].to_string())
+                } else {
+                    None
+                })
+        });
+
+    match timestamp.and_then(|timestamp| parse_timestamp(&timestamp)) {
+        Some(0) | None => None,
+        Some(seconds) => Some(seconds),
+    }
+}
+
+fn is_youtube_id(id: &str) -> bool {
+    id.len() == 11 && id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+fn recognize_youtube(url: &Url, segments: &[&str]) -> Option<String> {
+    let id = if url.host_str() == Some("youtu.be") {
+        match segments.first() {
+            Some(id) => id.to_string(),
+            None => return None,
+        }
+    } else if segments.len() == 1 && segments[0] == "watch" {
+        match url.query_pairs().find(|param| param.0 == "v") {
+            Some((_, id)) => id.into_owned(),
+            None => return None,
+        }
+    } else if segments.len() == 2 && (segments[0] == "shorts" || segments[0] == "embed") {
+        segments[1].to_string()
+    } else {
+        return None;
+    };
+
+    if is_youtube_id(&id) {
+        Some(format!("https://www.youtube.com/watch?v={}", id))
+    } else {
+        None
+    }
+}
+
+fn recognize_vimeo(segments: &[&str]) -> Option<String> {
+    if segments.len() == 1 && segments[0].chars().all(|c| c.is_digit(10)) {
+        Some(format!("https://vimeo.com/{}", segments[0]))
+    } else {
+        None
+    }
+}
+
+/// The first segment of SoundCloud paths that aren't users.
+const SOUNDCLOUD_RESERVED: &'static [&'static str] = &[
+    "charts",
+    "discover",
+    "jobs",
+    "pages",
+    "search",
+    "settings",
+    "stream",
+    "upload",
+    "you",
+];
+
+fn recognize_soundcloud(segments: &[&str]) -> Option<String> {
+    let is_media = match segments.len() {
+        // A track.
+        2 => segments[1] != "sets",
+        // A playlist.
+        3 => segments[1] == "sets",
+        _ => false,
+    };
+
+    if is_media && !SOUNDCLOUD_RESERVED.contains(&segments[0]) {
+        Some(format!("https://soundcloud.com/{}", segments.join("/")))
+    } else {
+        None
+    }
+}
+
+/// Figure out which provider's media a URL refers to, if any.
+pub fn recognize(url: &Url) -> Option<Target> {
+    let segments: Vec<&str> = match url.path_segments() {
+        Some(segments) => segments.filter(|segment| !segment.is_empty()).collect(),
+        None => return None,
+    };
+
+    let (provider, media_url) = match url.host_str() {
+        Some("youtu.be") |
+        Some("youtube.com") |
+        Some("www.youtube.com") |
+        Some("m.youtube.com") |
+        Some("music.youtube.com") => (&YOUTUBE, recognize_youtube(url, &segments)),
+        Some("vimeo.com") | Some("www.vimeo.com") => (&VIMEO, recognize_vimeo(&segments)),
+        Some("soundcloud.com") | Some("www.soundcloud.com") | Some("m.soundcloud.com") => {
+            (&SOUNDCLOUD, recognize_soundcloud(&segments))
+        }
+        _ => return None,
+    };
+
+    media_url.map(|media_url| {
+        Target {
+            provider,
+            url: media_url,
+            start: start(url),
+        }
+    })
+}
+
+#[test]
+fn test_parse_timestamp() {
+    let cases = vec![
+        ("83", Some(83)),
+        ("83s", Some(83)),
+        ("1m23s", Some(83)),
+        ("1m23", Some(83)),
+        ("1h2m3s", Some(3723)),
+        ("1:23", Some(83)),
+        ("1:02:03", Some(3723)),
+        ("", None),
+        ("1x", None),
+        ("m", None),
+        ("1::3", None),
+        ("99999999999999999999", None),
+        ("5124095576030432h", None),
+        ("5124095576030431h9999s", None),
+        ("307445734561825861:0", None),
+        ("307445734561825860:59", None),
+    ];
+
+    for (timestamp, expected) in cases {
+        assert_eq!(expected, parse_timestamp(timestamp), "parsing {:?}", timestamp);
+    }
+}
+
+#[test]
+fn test_recognize() {
+    let youtube = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+    let cases = vec![
+        ("https://www.youtube.com/watch?v=dQw4w9WgXcQ", Some((&YOUTUBE, youtube, None))),
+        ("https://youtu.be/dQw4w9WgXcQ?t=83", Some((&YOUTUBE, youtube, Some(83)))),
+        ("https://m.youtube.com/watch?feature=share&v=dQw4w9WgXcQ&t=1m23s",
+         Some((&YOUTUBE, youtube, Some(83)))),
+        ("https://www.youtube.com/watch?v=dQw4w9WgXcQ#t=1h", Some((&YOUTUBE, youtube, Some(3600)))),
+        ("https://youtu.be/dQw4w9WgXcQ?t=99999999999999999999", Some((&YOUTUBE, youtube, None))),
+        ("https://youtube.com/shorts/dQw4w9WgXcQ", Some((&YOUTUBE, youtube, None))),
+        ("https://www.youtube.com/embed/dQw4w9WgXcQ?start=0", Some((&YOUTUBE, youtube, None))),
+        ("https://www.youtube.com/watch?v=tooshort", None),
+        ("https://www.youtube.com/channel/UCuAXFkgsw1L7xaCfnd5JJOw", None),
+        ("https://youtu.be/", None),
+        ("https://vimeo.com/76979871#t=1m5s",
+         Some((&VIMEO, "https://vimeo.com/76979871", Some(65)))),
+        ("https://vimeo.com/channels", None),
+        ("https://soundcloud.com/forss/flickermood#t=0:30",
+         Some((&SOUNDCLOUD, "https://soundcloud.com/forss/flickermood", Some(30)))),
+        ("https://soundcloud.com/forss/sets/soulhack",
+         Some((&SOUNDCLOUD, "https://soundcloud.com/forss/sets/soulhack", None))),
+        ("https://soundcloud.com/forss", None),
+        ("https://soundcloud.com/discover/sets", None),
+        ("https://example.com/watch?v=dQw4w9WgXcQ", None),
+    ];
+
+    for (url, expected) in cases {
+        let parsed = Url::parse(url).expect("Couldn't parse test URL");
+
+        let expected = expected.map(|(provider, media_url, start)| {
+            Target {
+                provider,
+                url: media_url.to_string(),
+                start,
+            }
+        });
+
+        assert_eq!(expected, recognize(&parsed), "recognizing {}", url);
+    }
+}
@@ -0,0 +1,30 @@
+use url;
+
+use util::http;
+
+mod client;
+mod embed;
+mod link;
+mod provider;
+mod previewer;
+
+pub use self::client::Client;
+pub use self::link::{Target, recognize};
+pub use self::provider::Provider;
+pub use self::previewer::OEmbed;
+
+error_chain! {
+    links {
+        Http(http::Error, http::ErrorKind);
+    }
+
+    foreign_links {
+        Url(url::ParseError);
+    }
+
+    errors {
+        UnrecognizedUrl {
+            description("URL doesn't refer to media from a known oEmbed provider")
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use url::Url;
+
+use slog::Logger;
+
+use errors;
+use preview::{Preview, Card};
+use util::html_to_markdown::escape;
+
+use super::{ErrorKind, Result};
+
+use super::client::Client;
+use super::link::{self, Target};
+
+/// Format a number of seconds like a media player, e.g. `1:23` or `1:02:03`.
+fn format_duration(seconds: u64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// Previews media from the providers in the registry through their oEmbed
+/// endpoints.
+pub struct OEmbed {
+    client: Client,
+    only_when_better: bool,
+}
+
+impl OEmbed {
+    pub fn new(client: Client) -> OEmbed {
+        OEmbed {
+            client,
+            only_when_better: true,
+        }
+    }
+
+    /// Whether to skip links that Discord embeds on its own just as well,
+    /// i.e. those without a start time, which Discord's player ignores. This
+    /// is the default.
+    pub fn only_when_better(mut self, only_when_better: bool) -> OEmbed {
+        self.only_when_better = only_when_better;
+        self
+    }
+
+    pub fn preview_url(&self, url: &Url, log: &Logger) -> Result<Option<Card>> {
+        let target = link::recognize(url).ok_or(ErrorKind::UnrecognizedUrl)?;
+
+        info!(log, "Recognized oEmbed URL"; "target" => format!("{:?}", target));
+
+        if self.only_when_better && target.start.is_none() {
+            info!(log, "Leaving the preview to Discord's own embed");
+            return Ok(None);
+        }
+
+        self.preview_media(&target, url).map(Some)
+    }
+
+    pub fn preview_media(&self, target: &Target, url: &Url) -> Result<Card> {
+        let embed = self.client.get_embed(target.provider, &target.url)?;
+
+        let mut details = vec![];
+
+        match (embed.author_name(), embed.author_url()) {
+            (Some(name), Some(author_url)) => {
+                details.push(format!("[{}]({})", escape(name), author_url))
+            }
+            (Some(name), None) => details.push(escape(name)),
+            _ => {}
+        }
+
+        if let Some(duration) = embed.duration() {
+            details.push(format_duration(duration));
+        }
+
+        let mut description = details.join(" · ");
+
+        if let Some(start) = target.start {
+            if !description.is_empty() {
+                description.push_str("\n");
+            }
+
+            description.push_str(&format!("*Starts at {}.*", format_duration(start)));
+        }
+
+        let mut card = Card::new()
+            .url(url.as_str())
+            .color(target.provider.color)
+            .footer(target.provider.name, Some(target.provider.thumbnail));
+
+        if let Some(title) = embed.title() {
+            card = card.title(escape(title));
+        }
+
+        if !description.is_empty() {
+            card = card.description(description);
+        }
+
+        if let Some(thumbnail) = embed.thumbnail_url() {
+            card = card.image(thumbnail);
+        }
+
+        Ok(card)
+    }
+}
+
+impl Preview for OEmbed {
+    fn name(&self) -> &'static str {
+        "oEmbed"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        link::recognize(url).is_some()
+    }
+
+    fn preview(&self, url: &Url, log: &Logger) -> errors::Result<Option<Card>> {
+        self.preview_url(url, log).map_err(From::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use util::preview_fixture::{preview_fixture, try_preview_fixture};
+
+    use super::super::client;
+    use super::{OEmbed, format_duration};
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!("0:05", format_duration(5));
+        assert_eq!("1:23", format_duration(83));
+        assert_eq!("1:02:03", format_duration(3723));
+    }
+
+    #[test]
+    fn test_preview_youtube() {
+        let oembed = OEmbed::new(client::fixtures());
+
+        let card = preview_fixture(&oembed, "https://youtu.be/dQw4w9WgXcQ?t=83");
+
+        assert_eq!(
+            Some("Rick Astley - Never Gonna Give You Up (Official Music Video)"),
+            card.get_title()
+        );
+        assert_eq!(Some("https://youtu.be/dQw4w9WgXcQ?t=83"), card.get_url());
+        assert_eq!(
+            Some("[Rick Astley](https://www.youtube.com/@RickAstleyYT)\n*Starts at 1:23.*"),
+            card.get_description()
+        );
+        assert_eq!(
+            Some("https://i.ytimg.com/vi/dQw4w9WgXcQ/hqdefault.jpg"),
+            card.get_image()
+        );
+        assert_eq!("YouTube", card.get_footer().unwrap().text());
+    }
+
+    #[test]
+    fn test_preview_vimeo() {
+        let oembed = OEmbed::new(client::fixtures()).only_when_better(false);
+
+        let card = preview_fixture(&oembed, "https://vimeo.com/76979871");
+
+        assert_eq!(Some("The New Vimeo Player (You Know, For Videos)"), card.get_title());
+        assert_eq!(
+            Some("[Vimeo](https://vimeo.com/staff) · 1:02"),
+            card.get_description()
+        );
+    }
+
+    #[test]
+    fn test_only_when_better() {
+        let plain = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+        let partway = "https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=83";
+
+        let oembed = OEmbed::new(client::fixtures());
+
+        assert!(try_preview_fixture(&oembed, plain).expect("Couldn't preview video").is_none());
+        assert!(try_preview_fixture(&oembed, partway).expect("Couldn't preview video").is_some());
+
+        let oembed = OEmbed::new(client::fixtures()).only_when_better(false);
+
+        assert!(try_preview_fixture(&oembed, plain).expect("Couldn't preview video").is_some());
+    }
+}
@@ -0,0 +1,44 @@
+//! The sites whose links are previewed, and how to ask them about media.
+
+/// A site which describes its media through an oEmbed endpoint.
+///
+/// Discord embeds links to all of these on its own, with a player.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Provider {
+    /// A short, unique name for the provider, e.g. `youtube`.
+    pub id: &'static str,
+
+    pub name: &'static str,
+
+    /// The URL of the provider's oEmbed endpoint.
+    pub endpoint: &'static str,
+
+    pub color: u64,
+
+    pub thumbnail: &'static str,
+}
+
+pub static YOUTUBE: Provider = Provider {
+    id: "youtube",
+    name: "YouTube",
+    endpoint: "https://www.youtube.com/oembed",
+    color: 0xFF0000,
+    thumbnail: "https://www.youtube.com/favicon.ico",
+};
+
+pub static VIMEO: Provider = Provider {
+    id: "vimeo",
+    name: "Vimeo",
+    endpoint: "https://vimeo.com/api/oembed.json",
+    color: 0x1AB7EA,
+    thumbnail: "https://vimeo.com/favicon.ico",
+};
+
+pub static SOUNDCLOUD: Provider = Provider {
+    id: "soundcloud",
+    name: "SoundCloud",
+    endpoint: "https://soundcloud.com/oembed",
+    color: 0xFF5500,
+    thumbnail: "https://soundcloud.com/favicon.ico",
+};
+
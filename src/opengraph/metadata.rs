@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use html5ever::parse_document;
+use html5ever::rcdom::{NodeData, RcDom, Handle};
+use html5ever::tendril::TendrilSink;
+
+use url::Url;
+
+use util::dom::{attribute, text_content};
+
+/// The metadata a page describes itself with.
+///
+/// OpenGraph properties take precedence over Twitter Card properties, which
+/// take precedence over the page's `<title>` and `<meta name=description>`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metadata {
+    title: Option<String>,
+    description: Option<String>,
+    url: Option<String>,
+    image: Option<String>,
+    site_name: Option<String>,
+    large_image: bool,
+}
+
+/// Collects the `<title>` and `<meta>` properties of a document.
+#[derive(Default)]
+struct Collector {
+    title: Option<String>,
+
+    /// The content of each `<meta>` property or name, lowercased. The first
+    /// occurrence of each wins.
+    properties: HashMap<String, String>,
+}
+
+impl Collector {
+    fn visit(&mut self, node: &Handle) {
+        if let NodeData::Element { ref name, .. } = node.data {
+            match name.local {
+                local_name!("title") if self.title.is_none() => {
+                    self.title = Some(text_content(node));
+                }
+                local_name!("meta") => {
+                    let key = attribute(node, "property").or_else(|| attribute(node, "name"));
+
+                    if let (Some(key), Some(content)) = (key, attribute(node, "content")) {
+                        self.properties.entry(key.to_lowercase()).or_insert(content);
+                    }
+                }
+                // Nothing within these is metadata, and they can be huge.
+                local_name!("script") | local_name!("style") => return,
+                _ => {}
+            }
+        }
+
+        for child in node.children.borrow().iter() {
+            self.visit(child);
+        }
+    }
+
+    /// The first non-blank value among the given properties.
+    fn property(&self, keys: &[&str]) -> Option<String> {
+        keys.iter()
+            .filter_map(|key| self.properties.get(*key))
+            .filter_map(|value| non_blank(value))
+            .next()
+    }
+}
+
+/// Trim the text and collapse any runs of whitespace within it, which are
+/// insignificant in HTML, or `None` if nothing's left.
+fn non_blank(text: &str) -> Option<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if text.is_empty() { None } else { Some(text) }
+}
+
+impl Metadata {
+    /// Extract the metadata from an HTML document fetched from `base`, which
+    /// relative URLs are resolved against.
+    pub fn parse(html: &str, base: &Url) -> Metadata {
+        let dom = parse_document(RcDom::default(), Default::default()).one(html);
+
+        let mut collector = Collector::default();
+        collector.visit(&dom.document);
+
+        let resolve = |url: String| base.join(&url).ok().map(Url::into_string);
+
+        Metadata {
+            title: collector
+                .property(&["og:title", "twitter:title"])
+                .or_else(|| collector.title.as_ref().and_then(|title| non_blank(title))),
+            description: collector
+                .property(&["og:description", "twitter:description", "description"]),
+            url: collector.property(&["og:url"]).and_then(&resolve),
+            image: collector
+                .property(&[
+                    "og:image:secure_url",
+                    "og:image",
+                    "twitter:image",
+                    "twitter:image:src",
+                ])
+                .and_then(&resolve),
+            site_name: collector.property(&["og:site_name", "twitter:site"]),
+            large_image: collector.property(&["twitter:card"]).map_or(false, |card| {
+                card == "summary_large_image"
+            }),
+        }
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_ref().map(String::as_str)
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_ref().map(String::as_str)
+    }
+
+    /// The canonical URL of the page.
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_ref().map(String::as_str)
+    }
+
+    pub fn image(&self) -> Option<&str> {
+        self.image.as_ref().map(String::as_str)
+    }
+
+    pub fn site_name(&self) -> Option<&str> {
+        self.site_name.as_ref().map(String::as_str)
+    }
+
+    /// Whether the page asks for its image to be shown large rather than as a
+    /// thumbnail.
+    pub fn large_image(&self) -> bool {
+        self.large_image
+    }
+
+    /// Whether there's enough metadata to be worth previewing.
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.description.is_none()
+    }
+}
+
+#[test]
+fn test_metadata_parse() {
+    use std::fs::File;
+    use std::io::Read;
+    use std::path::Path;
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/opengraph/article.html");
+
+    let mut html = String::new();
+    File::open(path).unwrap().read_to_string(&mut html).unwrap();
+
+    let base = Url::parse("https://example.com/posts/42?ref=chat").unwrap();
+    let metadata = Metadata::parse(&html, &base);
+
+    assert_eq!(Some("Fearless Concurrency & You"), metadata.title());
+    assert_eq!(
+        Some("How Rust's ownership rules keep threads honest."),
+        metadata.description()
+    );
+    assert_eq!(Some("https://example.com/posts/42"), metadata.url());
+    assert_eq!(Some("https://example.com/images/ferris.png"), metadata.image());
+    assert_eq!(Some("Example Blog"), metadata.site_name());
+    assert!(metadata.large_image());
+}
+
+#[test]
+fn test_metadata_parse_fallbacks() {
+    let base = Url::parse("https://example.com/").unwrap();
+
+    let html = r#"<html><head>
+        <title>
+          Plain   Title
+        </title>
+        <meta name="description" content="Plain description">
+        <meta name="twitter:image" content="/logo.png">
+      </head><body><title>Not the title</title></body></html>"#;
+
+    let metadata = Metadata::parse(html, &base);
+
+    assert_eq!(Some("Plain Title"), metadata.title());
+    assert_eq!(Some("Plain description"), metadata.description());
+    assert_eq!(None, metadata.url());
+    assert_eq!(Some("https://example.com/logo.png"), metadata.image());
+    assert!(!metadata.large_image());
+
+    assert!(Metadata::parse("<p>Nothing here</p>", &base).is_empty());
+}
@@ -0,0 +1,16 @@
+//! Previews of arbitrary web pages from their OpenGraph and Twitter Card
+//! metadata.
+
+use util::http;
+
+mod metadata;
+mod previewer;
+
+pub use self::metadata::Metadata;
+pub use self::previewer::OpenGraph;
+
+error_chain! {
+    links {
+        Http(http::Error, http::ErrorKind);
+    }
+}
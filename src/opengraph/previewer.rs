@@ -0,0 +1,193 @@
+use url::Url;
+
+use slog::Logger;
+
+use errors;
+use preview::{Preview, Card};
+use util::html_to_markdown;
+use util::http::{self, Limits, is_local_host};
+use util::truncate::truncate;
+
+use super::Result;
+use super::metadata::Metadata;
+
+/// The most characters of a page's description shown in its preview.
+pub const DESCRIPTION_LENGTH: usize = 500;
+
+/// Whether two URLs are on the same site, ignoring any `www.` prefix.
+fn same_site(a: &Url, b: &Url) -> bool {
+    let site = |url: &Url| url.host_str().map(|host| host.trim_left_matches("www.").to_string());
+
+    site(a).is_some() && site(a) == site(b)
+}
+
+/// A fallback previewer for any web page, based on the OpenGraph and Twitter
+/// Card metadata that sites provide for exactly this purpose.
+pub struct OpenGraph {
+    limits: Limits,
+}
+
+impl OpenGraph {
+    pub fn new() -> OpenGraph {
+        OpenGraph { limits: Limits::default() }
+    }
+
+    /// The limits on fetching each page.
+    pub fn limits(mut self, limits: Limits) -> OpenGraph {
+        self.limits = limits;
+        self
+    }
+
+    pub fn preview_url(&self, url: &Url, log: &Logger) -> Result<Option<Card>> {
+        // Every hop of any redirects is checked as well.
+        let limits = Limits {
+            public_only: true,
+            ..self.limits
+        };
+
+        let page = match http::get_html(url.as_str(), limits) {
+            Ok(Some(page)) => page,
+            Ok(None) => {
+                info!(log, "Not an HTML page");
+                return Ok(None);
+            }
+            Err(http::Error(http::ErrorKind::LocalAddress(url), _)) => {
+                info!(log, "Refusing to fetch a local address"; "url" => url);
+                return Ok(None);
+            }
+            Err(e) => bail!(e),
+        };
+
+        let metadata = Metadata::parse(page.body(), page.url());
+
+        Ok(card(&metadata, page.url()))
+    }
+}
+
+/// The URL that a page gives for itself, as long as it's on the same site
+/// that it was fetched from, since a page can claim to be anywhere.
+fn canonical_url(metadata: &Metadata, url: &Url) -> Option<Url> {
+    let canonical = match metadata.url().and_then(|canonical| Url::parse(canonical).ok()) {
+        Some(canonical) => canonical,
+        None => return None,
+    };
+
+    let is_web = canonical.scheme() == "http" || canonical.scheme() == "https";
+
+    if is_web && same_site(&canonical, url) {
+        Some(canonical)
+    } else {
+        None
+    }
+}
+
+/// Render a page's metadata, or `None` if there's too little to bother.
+fn card(metadata: &Metadata, url: &Url) -> Option<Card> {
+    if metadata.is_empty() {
+        return None;
+    }
+
+    let canonical = canonical_url(metadata, url);
+
+    let mut card = Card::new()
+        .url(canonical.as_ref().unwrap_or(url).as_str())
+        .footer(metadata.site_name().or(url.host_str()).unwrap_or(""), None);
+
+    if let Some(title) = metadata.title() {
+        card = card.title(title);
+    }
+
+    if let Some(description) = metadata.description() {
+        card = card.description(truncate(
+            &html_to_markdown::escape(description),
+            DESCRIPTION_LENGTH,
+            None,
+        ));
+    }
+
+    match metadata.image() {
+        Some(image) if metadata.large_image() => card = card.image(image),
+        Some(image) => card = card.thumbnail(image),
+        None => {}
+    }
+
+    Some(card)
+}
+
+impl Preview for OpenGraph {
+    fn name(&self) -> &'static str {
+        "OpenGraph"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        (url.scheme() == "http" || url.scheme() == "https") &&
+            url.host().map_or(false, |host| !is_local_host(host))
+    }
+
+    fn preview(&self, url: &Url, log: &Logger) -> errors::Result<Option<Card>> {
+        self.preview_url(url, log).map_err(From::from)
+    }
+
+    fn is_fallback(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn test_matches() {
+    let og = OpenGraph::new();
+
+    assert!(og.matches(&Url::parse("https://example.com/posts/42").unwrap()));
+    assert!(og.matches(&Url::parse("http://93.184.216.34/").unwrap()));
+    assert!(!og.matches(&Url::parse("ftp://example.com/file").unwrap()));
+    assert!(!og.matches(&Url::parse("http://localhost:8080/admin").unwrap()));
+    assert!(!og.matches(&Url::parse("http://192.168.1.1/").unwrap()));
+    assert!(!og.matches(&Url::parse("http://[::1]/").unwrap()));
+}
+
+#[test]
+fn test_card() {
+    let url = Url::parse("https://example.com/posts/42?ref=chat").unwrap();
+
+    let html = r#"<meta property="og:title" content="A *starred* title">
+        <meta property="og:description" content="Mind the **stars**, @everyone">
+        <meta property="og:image" content="/thumb.png">"#;
+
+    let preview = card(&Metadata::parse(html, &url), &url).expect("Page had no preview");
+
+    assert_eq!(Some("A *starred* title"), preview.get_title());
+    assert_eq!(Some("https://example.com/posts/42?ref=chat"), preview.get_url());
+    assert_eq!(
+        Some("Mind the \\*\\*stars\\*\\*, @\u{200B}everyone"),
+        preview.get_description()
+    );
+    assert_eq!(Some("https://example.com/thumb.png"), preview.get_thumbnail());
+    assert_eq!(None, preview.get_image());
+    assert_eq!(Some("example.com"), preview.get_footer().map(|f| f.text()));
+
+    assert_eq!(None, card(&Metadata::parse("<p>Hi</p>", &url), &url));
+}
+
+#[test]
+fn test_card_url() {
+    let url = Url::parse("https://example.com/posts/42?ref=chat").unwrap();
+
+    let card_url = |og_url: &str| {
+        let html = format!(
+            r#"<meta property="og:title" content="Title">
+            <meta property="og:url" content="{}">"#,
+            og_url
+        );
+
+        card(&Metadata::parse(&html, &url), &url)
+            .and_then(|card| card.get_url().map(String::from))
+    };
+
+    let same_site = "https://www.example.com/posts/42";
+    let relative = "/posts/42";
+
+    assert_eq!(Some(same_site.to_string()), card_url(same_site));
+    assert_eq!(Some("https://example.com/posts/42".to_string()), card_url(relative));
+    assert_eq!(Some(url.to_string()), card_url("https://evil.example.net/"));
+    assert_eq!(Some(url.to_string()), card_url("javascript:alert(1)"));
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use util::http::{self, Limits};
+
+use super::Result;
+
+use super::gist::{Gist, ExecuteRequest, Execution};
+use super::godbolt::ShortLink;
+
+pub const PLAYGROUND_BASE_URL: &'static str = "https://play.rust-lang.org";
+pub const GODBOLT_BASE_URL: &'static str = "https://godbolt.org";
+
+/// Talks to the Rust Playground and Compiler Explorer.
+pub struct Client {
+    playground_base_url: String,
+    godbolt_base_url: String,
+    limits: Limits,
+}
+
+impl Client {
+    pub fn new() -> Client {
+        Client::with_base_urls(PLAYGROUND_BASE_URL, GODBOLT_BASE_URL)
+    }
+
+    /// Create a client which talks to a playground at `playground_base_url`,
+    /// and a Compiler Explorer at `godbolt_base_url`, instead of the public
+    /// ones.
+    pub fn with_base_urls<P, G>(playground_base_url: P, godbolt_base_url: G) -> Client
+    where
+        P: Into<String>,
+        G: Into<String>,
+    {
+        Client {
+            playground_base_url: playground_base_url.into().trim_right_matches('/').to_string(),
+            godbolt_base_url: godbolt_base_url.into().trim_right_matches('/').to_string(),
+            limits: Limits::default(),
+        }
+    }
+
+    pub fn get_gist(&self, id: &str) -> Result<Gist> {
+        let url = format!("{}/meta/gist/{}", self.playground_base_url, id);
+
+        Ok(http::get_json(&url, self.limits)?)
+    }
+
+    /// Build and run code on the playground.
+    pub fn execute(&self, request: &ExecuteRequest) -> Result<Execution> {
+        let url = format!("{}/execute", self.playground_base_url);
+
+        // Compiling takes a while.
+        let limits = Limits {
+            timeout: Duration::from_secs(30),
+            ..self.limits
+        };
+
+        Ok(http::post_json(&url, request, limits)?)
+    }
+
+    pub fn get_short_link(&self, id: &str) -> Result<ShortLink> {
+        let url = format!("{}/api/shortlinkinfo/{}", self.godbolt_base_url, id);
+
+        Ok(http::get_json(&url, self.limits)?)
+    }
+}
+
+/// A `Client` talking to a server for the fixtures in
+/// `tests/fixtures/playground`, with the playground's under `play` and
+/// Compiler Explorer's under `godbolt`.
+#[cfg(test)]
+pub fn fixtures() -> Client {
+    use util::fixture_server::FixtureServer;
+
+    let server = FixtureServer::start("playground");
+
+    Client::with_base_urls(server.url("/play"), server.url("/godbolt"))
+}
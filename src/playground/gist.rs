@@ -0,0 +1,51 @@
+/// A gist as the playground serves it.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Gist {
+    code: String,
+}
+
+impl Gist {
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+}
+
+/// A request for the playground to build and run some code.
+#[derive(Clone, Debug, Serialize)]
+pub struct ExecuteRequest<'a> {
+    pub channel: &'a str,
+    pub mode: &'a str,
+    pub edition: &'a str,
+    #[serde(rename = "crateType")]
+    pub crate_type: &'a str,
+    pub tests: bool,
+    pub backtrace: bool,
+    pub code: &'a str,
+}
+
+/// What came of building and running some code on the playground.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Execution {
+    success: bool,
+
+    #[serde(default)]
+    stdout: String,
+
+    /// The compiler's output, followed by any the program wrote to stderr.
+    #[serde(default)]
+    stderr: String,
+}
+
+impl Execution {
+    pub fn is_success(&self) -> bool {
+        self.success
+    }
+
+    pub fn stdout(&self) -> &str {
+        &self.stdout
+    }
+
+    pub fn stderr(&self) -> &str {
+        &self.stderr
+    }
+}
@@ -0,0 +1,58 @@
+/// The state of Compiler Explorer that a short link saved.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ShortLink {
+    #[serde(default)]
+    sessions: Vec<Session>,
+}
+
+/// An editor, and the compilers its source was sent to.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Session {
+    /// e.g. `rust` or `c++`.
+    language: String,
+
+    source: String,
+
+    #[serde(default)]
+    compilers: Vec<Compiler>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Compiler {
+    /// e.g. `r1700` for rustc 1.70.0.
+    id: String,
+
+    #[serde(default)]
+    options: String,
+}
+
+impl ShortLink {
+    /// The first editor, which is usually the only one.
+    pub fn session(&self) -> Option<&Session> {
+        self.sessions.first()
+    }
+}
+
+impl Session {
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn compilers(&self) -> &[Compiler] {
+        &self.compilers
+    }
+}
+
+impl Compiler {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn options(&self) -> &str {
+        &self.options
+    }
+}
@@ -0,0 +1,149 @@
+use url::Url;
+
+/// The code that a URL refers to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// A gist shared from the Rust Playground, and how it was to be run.
+    ///
+    /// Options with values the playground doesn't know are `None`, so that
+    /// whatever was in the link never ends up in a preview.
+    Gist {
+        id: String,
+
+        /// e.g. `stable` or `nightly`, which is `stable` if the link doesn't
+        /// say.
+        channel: Option<String>,
+
+        /// e.g. `debug` or `release`, which is `debug` if the link doesn't
+        /// say.
+        mode: Option<String>,
+
+        /// e.g. `2018`, which links from before editions don't have.
+        edition: Option<String>,
+    },
+
+    /// A Compiler Explorer short link.
+    Godbolt { id: String },
+}
+
+fn is_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_alphanumeric())
+}
+
+const CHANNELS: &'static [&'static str] = &["stable", "beta", "nightly"];
+const MODES: &'static [&'static str] = &["debug", "release"];
+const EDITIONS: &'static [&'static str] = &["2015", "2018", "2021", "2024"];
+
+/// An option's value, if it's one of `values`.
+fn known(value: String, values: &[&str]) -> Option<String> {
+    if values.contains(&value.as_str()) {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Figure out which code a URL refers to, if any.
+pub fn recognize(url: &Url) -> Option<Target> {
+    match url.host_str() {
+        Some("play.rust-lang.org") | Some("play.integer32.com") => {
+            if url.path() != "/" {
+                return None;
+            }
+
+            let param = |name: &str| {
+                url.query_pairs()
+                    .find(|param| param.0 == name)
+                    .map(|param| param.1.into_owned())
+            };
+
+            match param("gist") {
+                Some(ref id) if is_id(id) => {
+                    Some(Target::Gist {
+                        id: id.clone(),
+                        channel: match param("version") {
+                            Some(channel) => known(channel, CHANNELS),
+                            None => Some("stable".to_string()),
+                        },
+                        mode: match param("mode") {
+                            Some(mode) => known(mode, MODES),
+                            None => Some("debug".to_string()),
+                        },
+                        edition: param("edition").and_then(|edition| known(edition, EDITIONS)),
+                    })
+                }
+                _ => None,
+            }
+        }
+        Some("godbolt.org") | Some("www.godbolt.org") | Some("rust.godbolt.org") => {
+            let segments: Vec<&str> = match url.path_segments() {
+                Some(segments) => segments.filter(|segment| !segment.is_empty()).collect(),
+                None => return None,
+            };
+
+            if segments.len() == 2 && segments[0] == "z" && is_id(segments[1]) {
+                Some(Target::Godbolt { id: segments[1].to_string() })
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+#[test]
+fn test_recognize() {
+    let gist = |id: &str, channel: Option<&str>, mode: Option<&str>, edition: Option<&str>| {
+        Target::Gist {
+            id: id.to_string(),
+            channel: channel.map(String::from),
+            mode: mode.map(String::from),
+            edition: edition.map(String::from),
+        }
+    };
+
+    let cases = vec![
+        (
+            "https://play.rust-lang.org/?version=nightly&mode=release&edition=2018&gist=3c5a1e3b",
+            Some(gist("3c5a1e3b", Some("nightly"), Some("release"), Some("2018"))),
+        ),
+        (
+            "https://play.rust-lang.org/?gist=3c5a1e3b&version=stable",
+            Some(gist("3c5a1e3b", Some("stable"), Some("debug"), None)),
+        ),
+        (
+            "https://play.integer32.com/?gist=3c5a1e3b",
+            Some(gist("3c5a1e3b", Some("stable"), Some("debug"), None)),
+        ),
+        ("https://play.rust-lang.org/?code=fn%20main()%20%7B%7D", None),
+        ("https://play.rust-lang.org/help?gist=3c5a1e3b", None),
+        ("https://play.rust-lang.org/?gist=../etc", None),
+        (
+            "https://play.rust-lang.org/?version=beta&mode=debug&edition=2024&gist=3c5a1e3b",
+            Some(gist("3c5a1e3b", Some("beta"), Some("debug"), Some("2024"))),
+        ),
+        (
+            "https://play.rust-lang.org/?version=%60%40everyone%60&gist=3c5a1e3b",
+            Some(gist("3c5a1e3b", None, Some("debug"), None)),
+        ),
+        (
+            "https://play.rust-lang.org/?mode=fast&gist=3c5a1e3b",
+            Some(gist("3c5a1e3b", Some("stable"), None, None)),
+        ),
+        (
+            "https://play.rust-lang.org/?edition=2019&gist=3c5a1e3b",
+            Some(gist("3c5a1e3b", Some("stable"), Some("debug"), None)),
+        ),
+        ("https://godbolt.org/z/Ke3aTh", Some(Target::Godbolt { id: "Ke3aTh".to_string() })),
+        ("https://rust.godbolt.org/z/Ke3aTh/", Some(Target::Godbolt { id: "Ke3aTh".to_string() })),
+        ("https://godbolt.org/z/", None),
+        ("https://godbolt.org/#g:!((g:!()))", None),
+        ("https://example.com/z/Ke3aTh", None),
+    ];
+
+    for (url, expected) in cases {
+        let parsed = Url::parse(url).expect("Couldn't parse test URL");
+
+        assert_eq!(expected, recognize(&parsed), "recognizing {}", url);
+    }
+}
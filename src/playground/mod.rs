@@ -0,0 +1,33 @@
+use util::http;
+
+mod client;
+mod gist;
+mod godbolt;
+mod link;
+mod previewer;
+
+pub use self::client::Client;
+pub use self::link::{Target, recognize};
+pub use self::previewer::Playground;
+
+pub const PLAYGROUND_THUMBNAIL: &'static str = "https://play.rust-lang.org/favicon.ico";
+pub const GODBOLT_THUMBNAIL: &'static str = "https://godbolt.org/favicon.ico";
+
+pub const RUST: u64 = 0xDEA584;
+pub const GODBOLT_GREEN: u64 = 0x67C52A;
+
+error_chain! {
+    links {
+        Http(http::Error, http::ErrorKind);
+    }
+
+    errors {
+        UnrecognizedUrl {
+            description("URL doesn't refer to a playground gist or Compiler Explorer link")
+        }
+        EmptyShortLink(id: String) {
+            description("The Compiler Explorer link has no source")
+            display("Compiler Explorer link {} has no source", id)
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use url::Url;
+
+use slog::Logger;
+
+use errors;
+use preview::{Preview, Card, Block};
+use util::truncate::{excerpt, CODE_LENGTH};
+
+use super::{ErrorKind, Result};
+
+use super::client::Client;
+use super::gist::ExecuteRequest;
+use super::link::{self, Target};
+
+/// The most lines of source shown in a preview.
+pub const MAX_LINES: usize = 30;
+
+/// The most lines of a program's output shown in a preview.
+pub const MAX_OUTPUT_LINES: usize = 15;
+
+/// The first `max_lines` of some text that fit in a preview, and a note on
+/// how it was cut, if it was.
+fn shorten(source: &str, max_lines: usize) -> (String, Option<String>) {
+    let lines = source.trim_right().lines().collect::<Vec<_>>();
+    let excerpt = excerpt(&lines, max_lines, CODE_LENGTH);
+
+    let note = if excerpt.cut_line {
+        Some(format!("cut at {} characters", CODE_LENGTH))
+    } else if excerpt.lines < lines.len() {
+        Some(format!("the first {} of {} lines", excerpt.lines, lines.len()))
+    } else {
+        None
+    };
+
+    (excerpt.source, note)
+}
+
+/// A Compiler Explorer language as a code block's language, where they
+/// differ.
+fn language_for(language: &str) -> &str {
+    match language {
+        "c++" => "cpp",
+        "cuda" => "cpp",
+        language => language,
+    }
+}
+
+pub struct Playground {
+    client: Client,
+    show_output: bool,
+}
+
+impl Playground {
+    pub fn new(client: Client) -> Playground {
+        Playground {
+            client,
+            show_output: false,
+        }
+    }
+
+    /// Whether to also run playground gists, and show what they output.
+    pub fn show_output(mut self, show_output: bool) -> Playground {
+        self.show_output = show_output;
+        self
+    }
+
+    pub fn preview_url(&self, url: &Url, log: &Logger) -> Result<Card> {
+        let target = link::recognize(url).ok_or(ErrorKind::UnrecognizedUrl)?;
+
+        info!(log, "Recognized playground URL"; "target" => format!("{:?}", target));
+
+        match target {
+            Target::Gist {
+                id,
+                channel,
+                mode,
+                edition,
+            } => {
+                self.preview_gist(
+                    url,
+                    &id,
+                    channel.as_ref().map(String::as_str),
+                    mode.as_ref().map(String::as_str),
+                    edition.as_ref().map(String::as_str),
+                    log,
+                )
+            }
+            Target::Godbolt { id } => self.preview_godbolt(url, &id),
+        }
+    }
+
+    pub fn preview_gist(
+        &self,
+        url: &Url,
+        id: &str,
+        channel: Option<&str>,
+        mode: Option<&str>,
+        edition: Option<&str>,
+        log: &Logger,
+    ) -> Result<Card> {
+        let gist = self.client.get_gist(id)?;
+
+        let (source, note) = shorten(gist.code(), MAX_LINES);
+
+        // Options the playground doesn't know are left out.
+        let mut description = vec![];
+
+        if let Some(channel) = channel {
+            description.push(format!("`{}` channel", channel));
+        }
+
+        if let Some(mode) = mode {
+            description.push(format!("`{}` mode", mode));
+        }
+
+        if let Some(edition) = edition {
+            description.push(format!("`{}` edition", edition));
+        }
+
+        if let Some(note) = note {
+            description.push(note);
+        }
+
+        let mut card = Card::new()
+            .url(url.as_str())
+            .title("Rust Playground")
+            .color(super::RUST)
+            .footer("Rust Playground", Some(super::PLAYGROUND_THUMBNAIL))
+            .block(Block::Code {
+                language: Some("rust".to_string()),
+                source,
+            });
+
+        if !description.is_empty() {
+            card = card.description(description.join(", "));
+        }
+
+        if self.show_output {
+            // The playground's defaults stand in for options it doesn't know.
+            let request = ExecuteRequest {
+                channel: channel.unwrap_or("stable"),
+                mode: mode.unwrap_or("debug"),
+                // Links from before editions ran as 2015 code.
+                edition: edition.unwrap_or("2015"),
+                crate_type: "bin",
+                tests: false,
+                backtrace: false,
+                code: gist.code(),
+            };
+
+            match self.client.execute(&request) {
+                Ok(execution) => {
+                    // The compiler's errors are more useful than no output.
+                    let output = if execution.is_success() {
+                        execution.stdout()
+                    } else {
+                        execution.stderr()
+                    };
+
+                    let (output, note) = shorten(output, MAX_OUTPUT_LINES);
+
+                    let mut intro = if execution.is_success() {
+                        "Output".to_string()
+                    } else {
+                        "Failed to run".to_string()
+                    };
+
+                    if let Some(note) = note {
+                        intro.push_str(&format!(", {}", note));
+                    }
+
+                    if output.is_empty() {
+                        card = card.block(Block::Text(format!("{}: *none*", intro)));
+                    } else {
+                        card = card.block(Block::Text(format!("{}:", intro))).block(Block::Code {
+                            language: None,
+                            source: output,
+                        });
+                    }
+                }
+                Err(e) => {
+                    warn!(log, "Couldn't run gist"; "error" => e.to_string());
+                }
+            }
+        }
+
+        Ok(card)
+    }
+
+    pub fn preview_godbolt(&self, url: &Url, id: &str) -> Result<Card> {
+        let short_link = self.client.get_short_link(id)?;
+
+        let session = short_link.session().ok_or_else(|| {
+            ErrorKind::EmptyShortLink(id.to_string())
+        })?;
+
+        let (source, note) = shorten(session.source(), MAX_LINES);
+
+        let compilers = session
+            .compilers()
+            .iter()
+            .map(|compiler| if compiler.options().trim().is_empty() {
+                format!("`{}`", compiler.id())
+            } else {
+                format!("`{}` with `{}`", compiler.id(), compiler.options().trim())
+            })
+            .collect::<Vec<_>>();
+
+        let mut description = if compilers.is_empty() {
+            "Not compiled".to_string()
+        } else {
+            format!("Compiled by {}", compilers.join(", "))
+        };
+
+        if let Some(note) = note {
+            description.push_str(&format!(", {}", note));
+        }
+
+        Ok(Card::new()
+            .url(url.as_str())
+            .title("Compiler Explorer")
+            .description(description)
+            .color(super::GODBOLT_GREEN)
+            .footer("Compiler Explorer", Some(super::GODBOLT_THUMBNAIL))
+            .block(Block::Code {
+                language: Some(language_for(session.language()).to_string()),
+                source,
+            }))
+    }
+}
+
+impl Preview for Playground {
+    fn name(&self) -> &'static str {
+        "Playground"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        link::recognize(url).is_some()
+    }
+
+    fn preview(&self, url: &Url, log: &Logger) -> errors::Result<Option<Card>> {
+        self.preview_url(url, log).map(Some).map_err(From::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use preview::Block;
+    use util::preview_fixture::preview_fixture;
+    use util::truncate::CODE_LENGTH;
+
+    use super::super::client;
+    use super::{Playground, MAX_LINES, shorten};
+
+    #[test]
+    fn test_shorten() {
+        assert_eq!(("a\nb\nc".to_string(), None), shorten("a\nb\nc\n\n", MAX_LINES));
+        assert_eq!((String::new(), None), shorten("", MAX_LINES));
+
+        let long = (1..41).map(|i| i.to_string()).collect::<Vec<_>>().join("\n");
+        let (source, note) = shorten(&long, MAX_LINES);
+
+        assert_eq!(MAX_LINES, source.lines().count());
+        assert_eq!(Some("the first 30 of 40 lines".to_string()), note);
+
+        let minified = "x".repeat(CODE_LENGTH + 100);
+        let (source, note) = shorten(&minified, MAX_LINES);
+
+        assert_eq!(CODE_LENGTH, source.len());
+        assert_eq!(Some("cut at 1900 characters".to_string()), note);
+    }
+
+    #[test]
+    fn test_preview_gist() {
+        let playground = Playground::new(client::fixtures());
+
+        let url = "https://play.rust-lang.org/\
+                   ?version=nightly&mode=release&edition=2018&gist=3c5a1e3b";
+        let card = preview_fixture(&playground, url);
+
+        assert_eq!(Some("Rust Playground"), card.get_title());
+        assert_eq!(
+            Some("`nightly` channel, `release` mode, `2018` edition"),
+            card.get_description()
+        );
+
+        assert_eq!(
+            vec![
+                Block::Code {
+                    language: Some("rust".to_string()),
+                    source: "fn main() {\n    println!(\"Hello, world!\");\n}".to_string(),
+                },
+            ],
+            card.body()
+        );
+    }
+
+    #[test]
+    fn test_preview_gist_defaults() {
+        let playground = Playground::new(client::fixtures());
+
+        let card = preview_fixture(&playground, "https://play.rust-lang.org/?gist=3c5a1e3b");
+
+        assert_eq!(Some("`stable` channel, `debug` mode"), card.get_description());
+        assert_eq!(1, card.body().len());
+    }
+
+    #[test]
+    fn test_preview_gist_unknown_options() {
+        let playground = Playground::new(client::fixtures());
+
+        let url = "https://play.rust-lang.org/\
+                   ?version=%60%40everyone%60&edition=2019&gist=3c5a1e3b";
+        let card = preview_fixture(&playground, url);
+
+        assert_eq!(Some("`debug` mode"), card.get_description());
+    }
+
+    #[test]
+    fn test_preview_gist_output() {
+        let playground = Playground::new(client::fixtures()).show_output(true);
+
+        let card = preview_fixture(&playground, "https://play.rust-lang.org/?gist=3c5a1e3b");
+
+        assert_eq!(Some("`stable` channel, `debug` mode"), card.get_description());
+        assert_eq!(3, card.body().len());
+        assert_eq!(Block::Text("Output:".to_string()), card.body()[1]);
+        assert_eq!(
+            Block::Code {
+                language: None,
+                source: "Hello, world!".to_string(),
+            },
+            card.body()[2]
+        );
+    }
+
+    #[test]
+    fn test_preview_godbolt() {
+        let playground = Playground::new(client::fixtures());
+
+        let card = preview_fixture(&playground, "https://godbolt.org/z/Ke3aTh");
+
+        assert_eq!(Some("Compiler Explorer"), card.get_title());
+        assert_eq!(Some("Compiled by `r1700` with `-C opt-level=3`"), card.get_description());
+        assert_eq!(
+            Block::Code {
+                language: Some("rust".to_string()),
+                source: "pub fn square(num: i32) -> i32 {\n    num * num\n}".to_string(),
+            },
+            card.body()[0]
+        );
+    }
+}
@@ -1,16 +1,189 @@
+use chrono::{DateTime, Utc};
 use url::Url;
-use serenity::model::Message;
 use slog::Logger;
 
-// TODO
-// Also pass the slog Logger as a parameter, which will already include all of
-// the necessary information.
+use errors::Result;
+
 // NOTE
 // I feel like the previewers system should instead work by being given a
 // constructor for a type that implements Preview, so that each "request" is
 // done from scratch?
 // This probably isn't possible without impl Trait, and perhaps even then.
 /// This trait represents a type that can preview a URL.
+///
+/// Previewers don't send anything themselves. Instead they produce a `Card`
+/// which the caller is free to render however it wants, e.g. as a Discord
+/// embed.
 pub trait Preview {
-    fn preview(&self, url: &Url, message: &Message, log: &Logger);
+    /// A short, human-readable name for this previewer, used for logging.
+    fn name(&self) -> &'static str;
+
+    /// Whether this previewer knows how to preview the given URL.
+    ///
+    /// This should be cheap, i.e. it shouldn't perform any requests, since
+    /// it's called for every previewer on every URL.
+    fn matches(&self, url: &Url) -> bool;
+
+    /// Produce a `Card` for the given URL.
+    ///
+    /// This is only called for URLs for which `matches` returned `true`.
+    /// `Ok(None)` means that there turned out to be nothing worth previewing.
+    fn preview(&self, url: &Url, log: &Logger) -> Result<Option<Card>>;
+
+    /// Whether this previewer is a generic fallback, only to be tried when
+    /// no site-specific previewer matches the URL.
+    fn is_fallback(&self) -> bool {
+        false
+    }
+}
+
+/// A rendering-agnostic preview of a URL.
+///
+/// This roughly mirrors the shape of a Discord embed, with the addition of
+/// body blocks which are meant to be displayed after the card itself.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Card {
+    title: Option<String>,
+    url: Option<String>,
+    description: Option<String>,
+    color: Option<u64>,
+    thumbnail: Option<String>,
+    image: Option<String>,
+    timestamp: Option<DateTime<Utc>>,
+    footer: Option<Footer>,
+    body: Vec<Block>,
+    nsfw: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Footer {
+    text: String,
+    icon_url: Option<String>,
+}
+
+/// A block of content that accompanies a `Card`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Block {
+    /// Plain Markdown text.
+    Text(String),
+    /// Markdown text quoted from the previewed resource, e.g. a comment body.
+    Quote(String),
+    /// Source code, highlighted as `language` if given, e.g. `rust`.
+    Code {
+        language: Option<String>,
+        source: String,
+    },
+}
+
+impl Card {
+    pub fn new() -> Card {
+        Card::default()
+    }
+
+    pub fn title<S: Into<String>>(mut self, title: S) -> Card {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn url<S: Into<String>>(mut self, url: S) -> Card {
+        self.url = Some(url.into());
+        self
+    }
+
+    pub fn description<S: Into<String>>(mut self, description: S) -> Card {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn color(mut self, color: u64) -> Card {
+        self.color = Some(color);
+        self
+    }
+
+    /// A small image shown alongside the description.
+    pub fn thumbnail<S: Into<String>>(mut self, thumbnail: S) -> Card {
+        self.thumbnail = Some(thumbnail.into());
+        self
+    }
+
+    /// A large image shown below the description.
+    pub fn image<S: Into<String>>(mut self, image: S) -> Card {
+        self.image = Some(image.into());
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: DateTime<Utc>) -> Card {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn footer<S: Into<String>>(mut self, text: S, icon_url: Option<&str>) -> Card {
+        self.footer = Some(Footer {
+            text: text.into(),
+            icon_url: icon_url.map(String::from),
+        });
+        self
+    }
+
+    pub fn block(mut self, block: Block) -> Card {
+        self.body.push(block);
+        self
+    }
+
+    /// Mark the card as Not Safe For Work, so that it's only shown where
+    /// that's allowed.
+    pub fn nsfw(mut self, nsfw: bool) -> Card {
+        self.nsfw = nsfw;
+        self
+    }
+
+    pub fn get_title(&self) -> Option<&str> {
+        self.title.as_ref().map(String::as_str)
+    }
+
+    pub fn get_url(&self) -> Option<&str> {
+        self.url.as_ref().map(String::as_str)
+    }
+
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_ref().map(String::as_str)
+    }
+
+    pub fn get_color(&self) -> Option<u64> {
+        self.color
+    }
+
+    pub fn get_thumbnail(&self) -> Option<&str> {
+        self.thumbnail.as_ref().map(String::as_str)
+    }
+
+    pub fn get_image(&self) -> Option<&str> {
+        self.image.as_ref().map(String::as_str)
+    }
+
+    pub fn get_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.timestamp
+    }
+
+    pub fn get_footer(&self) -> Option<&Footer> {
+        self.footer.as_ref()
+    }
+
+    pub fn body(&self) -> &[Block] {
+        &self.body
+    }
+
+    pub fn is_nsfw(&self) -> bool {
+        self.nsfw
+    }
+}
+
+impl Footer {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn icon_url(&self) -> Option<&str> {
+        self.icon_url.as_ref().map(String::as_str)
+    }
 }
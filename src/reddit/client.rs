@@ -0,0 +1,111 @@
+use serde::de::IgnoredAny;
+
+use util::http::{self, Limits};
+
+use super::{ErrorKind, Result};
+
+use super::post::Post;
+use super::comment::Comment;
+
+pub const BASE_URL: &'static str = "https://www.reddit.com";
+
+/// A listing of things, as returned by most of the Reddit API.
+#[derive(Deserialize)]
+struct Listing {
+    data: ListingData,
+}
+
+#[derive(Deserialize)]
+struct ListingData {
+    children: Vec<Thing>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", content = "data")]
+enum Thing {
+    #[serde(rename = "t1")]
+    Comment(Comment),
+    #[serde(rename = "t3")]
+    Post(Post),
+    /// A placeholder for comments which weren't loaded.
+    #[serde(rename = "more")]
+    More(IgnoredAny),
+}
+
+/// Fetches posts and comments through the `.json` form of their pages.
+pub struct Client {
+    base_url: String,
+    limits: Limits,
+}
+
+impl Client {
+    pub fn new() -> Client {
+        Client::with_base_url(BASE_URL)
+    }
+
+    /// Create a client which talks to a site rooted at `base_url` instead of
+    /// Reddit.
+    pub fn with_base_url<S: Into<String>>(base_url: S) -> Client {
+        Client {
+            base_url: base_url.into().trim_right_matches('/').to_string(),
+            limits: Limits {
+                max_size: 2 * 1024 * 1024,
+                ..Limits::default()
+            },
+        }
+    }
+
+    /// Fetch a post, along with one of its comments if an `id` is given.
+    ///
+    /// A thread page's JSON is a pair of listings: one containing the post,
+    /// and another containing its comments, starting from the given one.
+    pub fn get_thread(
+        &self,
+        post: &str,
+        comment: Option<&str>,
+    ) -> Result<(Post, Option<Comment>)> {
+        // Replies aren't needed, so only fetch the top-most comment.
+        let url = match comment {
+            Some(id) => format!("{}/comments/{}/_/{}.json?depth=1", self.base_url, post, id),
+            None => format!("{}/comments/{}.json?depth=1&limit=1", self.base_url, post),
+        };
+
+        let (posts, comments): (Listing, Listing) = http::get_json(&url, self.limits)?;
+
+        let post = posts
+            .data
+            .children
+            .into_iter()
+            .filter_map(|thing| match thing {
+                Thing::Post(post) => Some(post),
+                _ => None,
+            })
+            .next()
+            .ok_or(ErrorKind::NotFound)?;
+
+        if comment.is_none() {
+            return Ok((post, None));
+        }
+
+        let comment = comments
+            .data
+            .children
+            .into_iter()
+            .filter_map(|thing| match thing {
+                Thing::Comment(comment) => Some(comment),
+                _ => None,
+            })
+            .next()
+            .ok_or(ErrorKind::NotFound)?;
+
+        Ok((post, Some(comment)))
+    }
+}
+
+/// A `Client` talking to a server for the fixtures in `tests/fixtures/reddit`.
+#[cfg(test)]
+pub fn fixtures() -> Client {
+    use util::fixture_server::FixtureServer;
+
+    Client::with_base_url(FixtureServer::start("reddit").url(""))
+}
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc, TimeZone};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Comment {
+    id: String,
+    author: String,
+    permalink: String,
+
+    /// The comment's text as HTML, which is itself HTML-escaped.
+    body_html: String,
+
+    #[serde(default)]
+    score: i64,
+
+    created_utc: f64,
+}
+
+impl Comment {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    /// The URL of the comment on Reddit.
+    pub fn url(&self) -> String {
+        format!("https://www.reddit.com{}", self.permalink)
+    }
+
+    pub fn body_html(&self) -> &str {
+        &self.body_html
+    }
+
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    /// When it was created, unless the timestamp is out of range.
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        Utc.timestamp_opt(self.created_utc as i64, 0).single()
+    }
+}
@@ -0,0 +1,94 @@
+use url::Url;
+
+/// The post or comment on Reddit that a URL refers to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    Post(String),
+    Comment { post: String, id: String },
+}
+
+/// Whether something looks like a Reddit ID, which are base 36 numbers.
+fn is_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| (c >= 'a' && c <= 'z') || (c >= '0' && c <= '9'))
+}
+
+/// Figure out which post or comment a URL refers to, if any.
+///
+/// This understands old, new and mobile Reddit, as well as `redd.it` short
+/// links.
+pub fn recognize(url: &Url) -> Option<Target> {
+    let host = match url.host_str() {
+        Some(host) => host,
+        None => return None,
+    };
+
+    let segments = url.path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect::<Vec<_>>())
+        .unwrap_or_else(Vec::new);
+
+    // https://redd.it/6x3k2p
+    if host == "redd.it" {
+        return if segments.len() == 1 && is_id(segments[0]) {
+            Some(Target::Post(segments[0].to_string()))
+        } else {
+            None
+        };
+    }
+
+    match host {
+        "reddit.com" | "www.reddit.com" | "old.reddit.com" | "new.reddit.com" |
+        "np.reddit.com" | "m.reddit.com" | "i.reddit.com" => {}
+        _ => return None,
+    }
+
+    // Posts can be within a subreddit or a user's profile, or neither, e.g.
+    // /r/rust/comments/6x3k2p/slug/dm4t7j2/ or /comments/6x3k2p
+    let comments = match segments.iter().position(|&s| s == "comments") {
+        Some(index) => index,
+        None => return None,
+    };
+
+    let post = match segments.get(comments + 1) {
+        Some(&post) if is_id(post) => post.to_string(),
+        _ => return None,
+    };
+
+    match segments.get(comments + 3) {
+        Some(&id) if is_id(id) => Some(Target::Comment { post, id: id.to_string() }),
+        Some(_) => None,
+        None => Some(Target::Post(post)),
+    }
+}
+
+#[test]
+fn test_recognize() {
+    let post = Some(Target::Post(String::from("6x3k2p")));
+    let comment = Some(Target::Comment {
+        post: String::from("6x3k2p"),
+        id: String::from("dm4t7j2"),
+    });
+
+    let cases = vec![
+        ("https://www.reddit.com/r/rust/comments/6x3k2p/announcing_rust_120/", post.clone()),
+        ("https://old.reddit.com/r/rust/comments/6x3k2p/announcing_rust_120", post.clone()),
+        ("https://new.reddit.com/r/rust/comments/6x3k2p/", post.clone()),
+        ("https://reddit.com/comments/6x3k2p", post.clone()),
+        ("https://www.reddit.com/user/someone/comments/6x3k2p/slug/", post.clone()),
+        ("https://redd.it/6x3k2p", post.clone()),
+        ("https://www.reddit.com/r/rust/comments/6x3k2p/announcing_rust_120/dm4t7j2/",
+         comment.clone()),
+        ("https://np.reddit.com/r/rust/comments/6x3k2p/announcing_rust_120/dm4t7j2/?context=3",
+         comment.clone()),
+        ("https://www.reddit.com/r/rust/", None),
+        ("https://www.reddit.com/r/rust/comments/", None),
+        ("https://www.reddit.com/r/rust/comments/6x3k2p/slug/NOT-AN-ID/", None),
+        ("https://redd.it/", None),
+        ("https://example.com/r/rust/comments/6x3k2p/", None),
+    ];
+
+    for (url, expected) in cases {
+        let parsed = Url::parse(url).expect("Couldn't parse test URL");
+
+        assert_eq!(expected, recognize(&parsed), "recognizing {}", url);
+    }
+}
@@ -0,0 +1,29 @@
+use util::http;
+
+mod client;
+mod post;
+mod comment;
+mod link;
+mod previewer;
+
+pub use self::client::Client;
+pub use self::link::{Target, recognize};
+pub use self::previewer::Reddit;
+
+pub const THUMBNAIL: &'static str = "https://www.redditstatic.com/icon.png";
+pub const ORANGE_RED: u64 = 0xFF4500;
+
+error_chain! {
+    links {
+        Http(http::Error, http::ErrorKind);
+    }
+
+    errors {
+        UnrecognizedUrl {
+            description("URL doesn't refer to a Reddit post or comment")
+        }
+        NotFound {
+            description("The post or comment wasn't in the response")
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc, TimeZone};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Post {
+    id: String,
+    title: String,
+    subreddit: String,
+    author: String,
+    permalink: String,
+
+    /// The linked URL, or the post itself for self-posts.
+    url: String,
+
+    #[serde(default)]
+    is_self: bool,
+
+    /// The self-post's text as HTML, which is itself HTML-escaped.
+    #[serde(default)]
+    selftext_html: Option<String>,
+
+    #[serde(default)]
+    score: i64,
+
+    #[serde(default)]
+    num_comments: u64,
+
+    #[serde(default)]
+    over_18: bool,
+
+    /// Either a URL or a placeholder such as `self`, `default` or `nsfw`.
+    #[serde(default)]
+    thumbnail: String,
+
+    created_utc: f64,
+}
+
+impl Post {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn subreddit(&self) -> &str {
+        &self.subreddit
+    }
+
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    /// The URL of the post on Reddit.
+    pub fn item_url(&self) -> String {
+        format!("https://www.reddit.com{}", self.permalink)
+    }
+
+    /// The linked URL, unless this is a self-post.
+    pub fn url(&self) -> Option<&str> {
+        if self.is_self { None } else { Some(&self.url) }
+    }
+
+    pub fn selftext_html(&self) -> Option<&str> {
+        self.selftext_html.as_ref().map(String::as_str)
+    }
+
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    pub fn num_comments(&self) -> u64 {
+        self.num_comments
+    }
+
+    /// Whether the post is marked Not Safe For Work.
+    pub fn is_nsfw(&self) -> bool {
+        self.over_18
+    }
+
+    pub fn thumbnail(&self) -> Option<&str> {
+        if self.thumbnail.starts_with("http") {
+            Some(&self.thumbnail)
+        } else {
+            None
+        }
+    }
+
+    /// When it was created, unless the timestamp is out of range.
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        Utc.timestamp_opt(self.created_utc as i64, 0).single()
+    }
+}
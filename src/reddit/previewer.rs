@@ -0,0 +1,216 @@
+use url::Url;
+
+use slog::Logger;
+
+use errors;
+use preview::{Preview, Card, Block};
+use util::dom;
+use util::html_to_markdown::{convert_logged, escape};
+use util::plural;
+use util::truncate::truncate;
+
+use super::{ErrorKind, Result};
+
+use super::client::Client;
+use super::comment::Comment;
+use super::link::{self, Target};
+use super::post::Post;
+
+/// The most characters of a comment's body shown in a comment preview.
+pub const COMMENT_LENGTH: usize = 1800;
+
+/// The most characters of a self-post's text shown in a post preview.
+pub const POST_TEXT_LENGTH: usize = 1000;
+
+pub struct Reddit {
+    client: Client,
+}
+
+impl Reddit {
+    pub fn new(client: Client) -> Reddit {
+        Reddit { client }
+    }
+
+    fn card(&self) -> Card {
+        Card::new()
+            .color(super::ORANGE_RED)
+            .footer("Reddit", Some(super::THUMBNAIL))
+    }
+
+    pub fn preview_url(&self, url: &Url, log: &Logger) -> Result<Card> {
+        let target = link::recognize(url).ok_or(ErrorKind::UnrecognizedUrl)?;
+
+        info!(log, "Recognized Reddit URL"; "target" => format!("{:?}", target));
+
+        match target {
+            Target::Post(id) => {
+                let (post, _) = self.client.get_thread(&id, None)?;
+
+                Ok(self.preview_post(post, log))
+            }
+            Target::Comment { post, id } => {
+                let (post, comment) = self.client.get_thread(&post, Some(&id))?;
+                let comment = comment.ok_or(ErrorKind::NotFound)?;
+
+                Ok(self.preview_comment(&post, comment, log))
+            }
+        }
+    }
+
+    pub fn preview_post(&self, post: Post, log: &Logger) -> Card {
+        let mut description = format!(
+            "Posted to **r/{}** by **u/{}**. {}. {}.",
+            escape(post.subreddit()),
+            escape(post.author()),
+            plural(post.score(), "point"),
+            plural(post.num_comments() as i64, "comment")
+        );
+
+        if post.is_nsfw() {
+            description.push_str(" **NSFW**.");
+        }
+
+        if let Some(url) = post.url() {
+            description.push_str(&format!(" [Link]({})", url));
+        }
+
+        if let Some(html) = post.selftext_html() {
+            description.push_str("\n\n");
+            description.push_str(&truncate(
+                &convert_logged(&dom::unescape(html), log),
+                POST_TEXT_LENGTH,
+                Some(&post.item_url()),
+            ));
+        }
+
+        let mut card = self.card()
+            .url(post.item_url())
+            .title(post.title())
+            .description(description)
+            .nsfw(post.is_nsfw());
+
+        if let Some(created) = post.created() {
+            card = card.timestamp(created);
+        }
+
+        if let Some(thumbnail) = post.thumbnail() {
+            card = card.thumbnail(thumbnail);
+        }
+
+        card
+    }
+
+    pub fn preview_comment(&self, post: &Post, comment: Comment, log: &Logger) -> Card {
+        let description = format!(
+            "by **u/{}** in **r/{}**. {}.",
+            escape(comment.author()),
+            escape(post.subreddit()),
+            plural(comment.score(), "point")
+        );
+
+        let body = truncate(
+            &convert_logged(&dom::unescape(comment.body_html()), log),
+            COMMENT_LENGTH,
+            Some(&comment.url()),
+        );
+
+        let mut card = self.card()
+            .url(comment.url())
+            .title(format!("Comment on: {}", post.title()))
+            .description(description)
+            .nsfw(post.is_nsfw())
+            .block(Block::Quote(body));
+
+        if let Some(created) = comment.created() {
+            card = card.timestamp(created);
+        }
+
+        card
+    }
+}
+
+impl Preview for Reddit {
+    fn name(&self) -> &'static str {
+        "Reddit"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        link::recognize(url).is_some()
+    }
+
+    fn preview(&self, url: &Url, log: &Logger) -> errors::Result<Option<Card>> {
+        self.preview_url(url, log).map(Some).map_err(From::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use preview::Block;
+    use util::preview_fixture::preview_fixture;
+
+    use super::super::client;
+    use super::Reddit;
+
+    #[test]
+    fn test_preview_post() {
+        let reddit = Reddit::new(client::fixtures());
+
+        let card = preview_fixture(&reddit, "https://old.reddit.com/r/rust/comments/6x3k2p/whats/");
+
+        assert_eq!(Some("What's everyone excited about in 1.20?"), card.get_title());
+        assert_eq!(
+            Some(
+                "https://www.reddit.com/r/rust/comments/6x3k2p/\
+                 whats_everyone_excited_about_in_120/",
+            ),
+            card.get_url()
+        );
+        assert_eq!(
+            Some(
+                "Posted to **r/rust** by **u/rusty\\_crab**. **312** points. **87** comments.\n\n\
+                 What are you all *most* excited about in `1.20`?",
+            ),
+            card.get_description()
+        );
+        assert_eq!(None, card.get_thumbnail());
+        assert!(!card.is_nsfw());
+    }
+
+    #[test]
+    fn test_preview_comment() {
+        let reddit = Reddit::new(client::fixtures());
+
+        let url = "https://www.reddit.com/r/rust/comments/6x3k2p/whats/dm4t7j2/";
+        let card = preview_fixture(&reddit, url);
+
+        assert_eq!(
+            Some("Comment on: What's everyone excited about in 1.20?"),
+            card.get_title()
+        );
+        assert_eq!(
+            Some("by **u/ferris** in **r/rust**. **1** point."),
+            card.get_description()
+        );
+        assert_eq!(
+            &[Block::Quote(String::from("Associated constants, **finally**."))],
+            card.body()
+        );
+    }
+
+    #[test]
+    fn test_preview_nsfw() {
+        let reddit = Reddit::new(client::fixtures());
+
+        let card = preview_fixture(&reddit, "https://redd.it/7q1nsf");
+
+        assert!(card.is_nsfw());
+        assert_eq!(
+            Some(
+                "Posted to **r/some\\_subreddit** by **u/someone**. **1** point. **0** comments. \
+                 **NSFW**. [Link](https://example.com/a-link)",
+            ),
+            card.get_description()
+        );
+        assert_eq!(None, card.get_thumbnail());
+    }
+}
@@ -0,0 +1,81 @@
+use serde::de::DeserializeOwned;
+
+use util::http::{self, Limits};
+
+use super::{ErrorKind, Result};
+
+use super::post::{Question, Answer};
+
+pub const API_BASE_URL: &'static str = "https://api.stackexchange.com/2.2";
+
+/// The wrapper around every Stack Exchange API response.
+#[derive(Deserialize)]
+struct Wrapper<T> {
+    items: Vec<T>,
+}
+
+/// Talks to the Stack Exchange API.
+pub struct Client {
+    base_url: String,
+}
+
+impl Client {
+    pub fn new() -> Client {
+        Client::with_base_url(API_BASE_URL)
+    }
+
+    /// Create a client which talks to an API rooted at `base_url` instead of
+    /// the official one, e.g. `http://localhost:8080/2.2`.
+    pub fn with_base_url<S: Into<String>>(base_url: S) -> Client {
+        Client {
+            base_url: base_url.into().trim_right_matches('/').to_string(),
+        }
+    }
+
+    /// Fetch the items at `path` on `site`, including their bodies.
+    fn get<T: DeserializeOwned>(&self, path: &str, site: &str, query: &str) -> Result<Vec<T>> {
+        let url = format!(
+            "{}{}?site={}&filter=withbody{}",
+            self.base_url,
+            path,
+            site,
+            query
+        );
+
+        let wrapper: Wrapper<T> = http::get_json(&url, Limits::default())?;
+
+        Ok(wrapper.items)
+    }
+
+    pub fn get_question(&self, site: &str, id: u64) -> Result<Question> {
+        self.get(&format!("/questions/{}", id), site, "")?
+            .pop()
+            .ok_or_else(|| ErrorKind::NotFound(id).into())
+    }
+
+    pub fn get_answer(&self, site: &str, id: u64) -> Result<Answer> {
+        self.get(&format!("/answers/{}", id), site, "")?
+            .pop()
+            .ok_or_else(|| ErrorKind::NotFound(id).into())
+    }
+
+    /// Fetch the highest voted answer to a question, if it has any.
+    pub fn get_top_answer(&self, site: &str, question: u64) -> Result<Option<Answer>> {
+        let answers = self.get(
+            &format!("/questions/{}/answers", question),
+            site,
+            "&sort=votes&order=desc&pagesize=1",
+        )?;
+
+        Ok(answers.into_iter().next())
+    }
+}
+
+/// A `Client` talking to a server for the fixtures in
+/// `tests/fixtures/stackexchange`.
+#[cfg(test)]
+pub fn fixtures() -> Client {
+    use util::fixture_server::FixtureServer;
+
+    Client::with_base_url(FixtureServer::start("stackexchange").url("/2.2"))
+}
@@ -0,0 +1,117 @@
+use url::Url;
+
+/// The question or answer on a Stack Exchange site that a URL refers to.
+///
+/// `site` is the site's API parameter, e.g. `stackoverflow` or `unix`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    Question { site: String, id: u64 },
+    Answer { site: String, id: u64 },
+}
+
+/// Sites which have their own domains rather than `*.stackexchange.com`.
+const SITES: &'static [(&'static str, &'static str)] = &[
+    ("stackoverflow.com", "stackoverflow"),
+    ("meta.stackoverflow.com", "meta.stackoverflow"),
+    ("ru.stackoverflow.com", "ru.stackoverflow"),
+    ("pt.stackoverflow.com", "pt.stackoverflow"),
+    ("es.stackoverflow.com", "es.stackoverflow"),
+    ("ja.stackoverflow.com", "ja.stackoverflow"),
+    ("serverfault.com", "serverfault"),
+    ("superuser.com", "superuser"),
+    ("askubuntu.com", "askubuntu"),
+    ("stackapps.com", "stackapps"),
+    ("mathoverflow.net", "mathoverflow.net"),
+];
+
+/// The API parameter of the site at `host`, if it's a Stack Exchange site.
+fn site(host: &str) -> Option<String> {
+    let host = host.trim_left_matches("www.");
+
+    if let Some(&(_, site)) = SITES.iter().find(|&&(domain, _)| domain == host) {
+        return Some(site.to_string());
+    }
+
+    // e.g. unix.stackexchange.com or meta.unix.stackexchange.com
+    let site = host.trim_right_matches(".stackexchange.com");
+
+    if site != host && !site.is_empty() {
+        Some(site.to_string())
+    } else {
+        None
+    }
+}
+
+/// Figure out which question or answer a URL refers to, if any.
+pub fn recognize(url: &Url) -> Option<Target> {
+    let site = match url.host_str().and_then(site) {
+        Some(site) => site,
+        None => return None,
+    };
+
+    let segments = url.path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect::<Vec<_>>())
+        .unwrap_or_else(Vec::new);
+
+    if segments.len() < 2 {
+        return None;
+    }
+
+    let id = match segments[1].parse::<u64>() {
+        Ok(id) => id,
+        Err(_) => return None,
+    };
+
+    match segments[0] {
+        "a" => Some(Target::Answer { site, id }),
+        // Answer permalinks are also of the form
+        // /questions/<question>/<slug>/<answer>#<answer>
+        "q" | "questions" => {
+            match segments.get(3).and_then(|answer| answer.parse::<u64>().ok()) {
+                Some(answer) => Some(Target::Answer { site, id: answer }),
+                None => Some(Target::Question { site, id }),
+            }
+        }
+        _ => None,
+    }
+}
+
+#[test]
+fn test_recognize() {
+    fn question(site: &str, id: u64) -> Option<Target> {
+        Some(Target::Question {
+            site: String::from(site),
+            id,
+        })
+    }
+
+    fn answer(site: &str, id: u64) -> Option<Target> {
+        Some(Target::Answer {
+            site: String::from(site),
+            id,
+        })
+    }
+
+    let cases = vec![
+        ("https://stackoverflow.com/questions/28392008/more-concise-hashmap-initialization",
+         question("stackoverflow", 28392008)),
+        ("https://stackoverflow.com/questions/28392008", question("stackoverflow", 28392008)),
+        ("https://stackoverflow.com/q/28392008", question("stackoverflow", 28392008)),
+        ("https://stackoverflow.com/a/28392068", answer("stackoverflow", 28392068)),
+        ("https://stackoverflow.com/questions/28392008/slug/28392068#28392068",
+         answer("stackoverflow", 28392068)),
+        ("https://unix.stackexchange.com/questions/12345/slug", question("unix", 12345)),
+        ("https://meta.stackoverflow.com/q/1", question("meta.stackoverflow", 1)),
+        ("https://mathoverflow.net/a/2", answer("mathoverflow.net", 2)),
+        ("https://superuser.com/questions/tagged/linux", None),
+        ("https://stackoverflow.com/users/1234/someone", None),
+        ("https://stackexchange.com/questions/1", None),
+        ("https://example.com/questions/1", None),
+    ];
+
+    for (url, expected) in cases {
+        let parsed = Url::parse(url).expect("Couldn't parse test URL");
+
+        assert_eq!(expected, recognize(&parsed), "recognizing {}", url);
+    }
+}
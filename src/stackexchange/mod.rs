@@ -0,0 +1,29 @@
+use util::http;
+
+mod client;
+mod post;
+mod link;
+mod previewer;
+
+pub use self::client::Client;
+pub use self::link::{Target, recognize};
+pub use self::previewer::StackExchange;
+
+pub const THUMBNAIL: &'static str = "https://cdn.sstatic.net/Sites/stackoverflow/img/favicon.ico";
+pub const ORANGE: u64 = 0xF48024;
+
+error_chain! {
+    links {
+        Http(http::Error, http::ErrorKind);
+    }
+
+    errors {
+        UnrecognizedUrl {
+            description("URL doesn't refer to a Stack Exchange question or answer")
+        }
+        NotFound(id: u64) {
+            description("The question or answer doesn't exist")
+            display("Question or answer {} doesn't exist", id)
+        }
+    }
+}
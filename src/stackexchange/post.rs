@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+use chrono::serde::ts_seconds;
+
+use util::dom;
+
+/// The author of a question or answer.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Owner {
+    /// The name of the user, which is HTML-escaped.
+    display_name: String,
+}
+
+impl Owner {
+    pub fn display_name(&self) -> String {
+        dom::unescape(&self.display_name)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Question {
+    question_id: u64,
+
+    /// The title of the question, which is HTML-escaped.
+    title: String,
+
+    link: String,
+
+    /// The body of the question as HTML, if it was requested.
+    #[serde(default)]
+    body: String,
+
+    #[serde(default)]
+    score: i64,
+
+    #[serde(default)]
+    answer_count: u64,
+
+    #[serde(default)]
+    accepted_answer_id: Option<u64>,
+
+    #[serde(default)]
+    tags: Vec<String>,
+
+    /// Absent if the user has been deleted.
+    #[serde(default)]
+    owner: Option<Owner>,
+
+    #[serde(with = "ts_seconds")]
+    creation_date: DateTime<Utc>,
+}
+
+impl Question {
+    pub fn id(&self) -> u64 {
+        self.question_id
+    }
+
+    pub fn title(&self) -> String {
+        dom::unescape(&self.title)
+    }
+
+    pub fn link(&self) -> &str {
+        &self.link
+    }
+
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    pub fn answer_count(&self) -> u64 {
+        self.answer_count
+    }
+
+    pub fn accepted_answer_id(&self) -> Option<u64> {
+        self.accepted_answer_id
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn owner(&self) -> Option<&Owner> {
+        self.owner.as_ref()
+    }
+
+    pub fn creation_date(&self) -> DateTime<Utc> {
+        self.creation_date
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Answer {
+    answer_id: u64,
+    question_id: u64,
+
+    /// The body of the answer as HTML, if it was requested.
+    #[serde(default)]
+    body: String,
+
+    #[serde(default)]
+    score: i64,
+
+    #[serde(default)]
+    is_accepted: bool,
+
+    /// Absent if the user has been deleted.
+    #[serde(default)]
+    owner: Option<Owner>,
+
+    #[serde(with = "ts_seconds")]
+    creation_date: DateTime<Utc>,
+}
+
+impl Answer {
+    pub fn id(&self) -> u64 {
+        self.answer_id
+    }
+
+    pub fn question_id(&self) -> u64 {
+        self.question_id
+    }
+
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.is_accepted
+    }
+
+    pub fn owner(&self) -> Option<&Owner> {
+        self.owner.as_ref()
+    }
+
+    pub fn creation_date(&self) -> DateTime<Utc> {
+        self.creation_date
+    }
+}
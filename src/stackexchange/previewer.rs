@@ -0,0 +1,226 @@
+use url::Url;
+
+use slog::Logger;
+
+use errors;
+use preview::{Preview, Card, Block};
+use util::html_to_markdown::{convert_logged, escape};
+use util::plural;
+use util::truncate::truncate;
+
+use super::{ErrorKind, Result};
+
+use super::client::Client;
+use super::link::{self, Target};
+use super::post::{Question, Answer, Owner};
+
+/// The most characters of an answer's body shown in a preview.
+pub const ANSWER_LENGTH: usize = 1500;
+
+fn describe_owner(owner: Option<&Owner>) -> String {
+    match owner {
+        Some(owner) => format!("**{}**", escape(&owner.display_name())),
+        None => String::from("a deleted user"),
+    }
+}
+
+pub struct StackExchange {
+    client: Client,
+}
+
+impl StackExchange {
+    pub fn new(client: Client) -> StackExchange {
+        StackExchange { client }
+    }
+
+    fn card(&self) -> Card {
+        Card::new()
+            .color(super::ORANGE)
+            .footer("Stack Exchange", Some(super::THUMBNAIL))
+    }
+
+    /// Quote an answer, truncated to fit, with a line introducing it.
+    fn answer_blocks(&self, intro: &str, answer: &Answer, url: &str, log: &Logger) -> Vec<Block> {
+        let mut intro = format!(
+            "{} by {}. {}.",
+            intro,
+            describe_owner(answer.owner()),
+            plural(answer.score(), "vote")
+        );
+
+        if answer.is_accepted() {
+            intro.push_str(" :white_check_mark: Accepted.");
+        }
+
+        let body = truncate(&convert_logged(answer.body(), log), ANSWER_LENGTH, Some(url));
+
+        vec![Block::Text(intro), Block::Quote(body)]
+    }
+
+    pub fn preview_url(&self, url: &Url, log: &Logger) -> Result<Card> {
+        let target = link::recognize(url).ok_or(ErrorKind::UnrecognizedUrl)?;
+
+        info!(log, "Recognized Stack Exchange URL"; "target" => format!("{:?}", target));
+
+        match target {
+            Target::Question { site, id } => {
+                let question = self.client.get_question(&site, id)?;
+                let top_answer = self.client.get_top_answer(&site, id)?;
+
+                Ok(self.preview_question(&question, top_answer, log))
+            }
+            Target::Answer { site, id } => {
+                let answer = self.client.get_answer(&site, id)?;
+                let question = self.client.get_question(&site, answer.question_id())?;
+
+                Ok(self.preview_answer(&question, &answer, url, log))
+            }
+        }
+    }
+
+    pub fn preview_question(
+        &self,
+        question: &Question,
+        top_answer: Option<Answer>,
+        log: &Logger,
+    ) -> Card {
+        let mut description = format!(
+            "{}. {}. Asked by {}.",
+            plural(question.score(), "vote"),
+            plural(question.answer_count() as i64, "answer"),
+            describe_owner(question.owner())
+        );
+
+        if question.accepted_answer_id().is_some() {
+            description.push_str(" Has an accepted answer.");
+        }
+
+        if !question.tags().is_empty() {
+            let tags = question
+                .tags()
+                .iter()
+                .map(|tag| format!("`{}`", tag))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            description.push_str(&format!("\nTags: {}", tags));
+        }
+
+        let mut card = self.card()
+            .url(question.link())
+            .title(question.title())
+            .description(description)
+            .timestamp(question.creation_date());
+
+        if let Some(answer) = top_answer {
+            for block in self.answer_blocks("Top answer", &answer, question.link(), log) {
+                card = card.block(block);
+            }
+        }
+
+        card
+    }
+
+    pub fn preview_answer(
+        &self,
+        question: &Question,
+        answer: &Answer,
+        url: &Url,
+        log: &Logger,
+    ) -> Card {
+        let description = format!(
+            "{}. Question asked by {}.",
+            plural(question.score(), "vote"),
+            describe_owner(question.owner())
+        );
+
+        let mut card = self.card()
+            .url(url.as_str())
+            .title(format!("Answer to: {}", question.title()))
+            .description(description)
+            .timestamp(answer.creation_date());
+
+        for block in self.answer_blocks("Answer", answer, url.as_str(), log) {
+            card = card.block(block);
+        }
+
+        card
+    }
+}
+
+impl Preview for StackExchange {
+    fn name(&self) -> &'static str {
+        "Stack Exchange"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        link::recognize(url).is_some()
+    }
+
+    fn preview(&self, url: &Url, log: &Logger) -> errors::Result<Option<Card>> {
+        self.preview_url(url, log).map(Some).map_err(From::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use preview::Block;
+    use util::preview_fixture::preview_fixture;
+
+    use super::super::client;
+    use super::StackExchange;
+
+    #[test]
+    fn test_preview_question() {
+        let stack_exchange = StackExchange::new(client::fixtures());
+
+        let url = "https://stackoverflow.com/questions/28392008/hashmap-literal";
+        let card = preview_fixture(&stack_exchange, url);
+
+        assert_eq!(Some("More concise HashMap initialization"), card.get_title());
+        assert_eq!(
+            Some(
+                "https://stackoverflow.com/questions/28392008/\
+                 more-concise-hashmap-initialization",
+            ),
+            card.get_url()
+        );
+        assert_eq!(
+            Some(
+                "**150** votes. **9** answers. Asked by **Some\\_Asker**. Has an accepted answer.\n\
+                 Tags: `rust`, `hashmap`",
+            ),
+            card.get_description()
+        );
+        assert_eq!(
+            &[
+                Block::Text(String::from(
+                    "Top answer by **Ferris & Co**. **120** votes. :white_check_mark: Accepted.",
+                )),
+                Block::Quote(String::from(
+                    "Use an array and `collect`:\n\n```\nlet map: HashMap<_, _> = \
+                     [(1, 2)].iter().cloned().collect();\n```",
+                )),
+            ],
+            card.body()
+        );
+    }
+
+    #[test]
+    fn test_preview_answer() {
+        let stack_exchange = StackExchange::new(client::fixtures());
+
+        let card = preview_fixture(&stack_exchange, "https://stackoverflow.com/a/28392068");
+
+        assert_eq!(
+            Some("Answer to: More concise HashMap initialization"),
+            card.get_title()
+        );
+        assert_eq!(Some("https://stackoverflow.com/a/28392068"), card.get_url());
+        assert_eq!(
+            Some("**150** votes. Question asked by **Some\\_Asker**."),
+            card.get_description()
+        );
+        assert_eq!(2, card.body().len());
+    }
+}
@@ -0,0 +1,85 @@
+//! Helpers for inspecting documents parsed by html5ever.
+
+use html5ever::{QualName, parse_fragment};
+use html5ever::rcdom::{NodeData, RcDom, Handle};
+use html5ever::tendril::TendrilSink;
+
+/// Get the value of an element's attribute.
+pub fn attribute(node: &Handle, name: &str) -> Option<String> {
+    if let NodeData::Element { ref attrs, .. } = node.data {
+        for attr in attrs.borrow().iter() {
+            if &attr.name.local == name {
+                return Some(attr.value.to_string());
+            }
+        }
+    }
+
+    None
+}
+
+/// Get the concatenation of all of the text within a node, as-is.
+pub fn text_content(node: &Handle) -> String {
+    let mut text = String::new();
+
+    if let NodeData::Text { ref contents } = node.data {
+        text.push_str(&contents.borrow());
+    }
+
+    for child in node.children.borrow().iter() {
+        text.push_str(&text_content(child));
+    }
+
+    text
+}
+
+/// Whether a node is text consisting only of whitespace, which is
+/// insignificant between block elements such as list items.
+pub fn is_whitespace(node: &Handle) -> bool {
+    if let NodeData::Text { ref contents } = node.data {
+        contents.borrow().chars().all(char::is_whitespace)
+    } else {
+        false
+    }
+}
+
+/// Whether an element has the given class.
+pub fn has_class(node: &Handle, class: &str) -> bool {
+    attribute(node, "class").map_or(false, |classes| {
+        classes.split_whitespace().any(|c| c == class)
+    })
+}
+
+/// Find the first element in document order matching a predicate.
+pub fn find<F>(node: &Handle, predicate: &F) -> Option<Handle>
+where
+    F: Fn(&Handle) -> bool,
+{
+    if let NodeData::Element { .. } = node.data {
+        if predicate(node) {
+            return Some(node.clone());
+        }
+    }
+
+    node.children
+        .borrow()
+        .iter()
+        .filter_map(|child| find(child, predicate))
+        .next()
+}
+
+/// Decode the character references in text, e.g. turning `&lt;p&gt;` into
+/// `<p>`, for APIs which serve HTML escaped within HTML.
+pub fn unescape(text: &str) -> String {
+    let context = QualName::new(None, ns!(html), local_name!("body"));
+    let dom = parse_fragment(RcDom::default(), Default::default(), context, vec![]).one(text);
+
+    text_content(&dom.document)
+}
+
+#[test]
+fn test_unescape() {
+    assert_eq!(
+        "<p>Fish & \"chips\"</p>",
+        unescape("&lt;p&gt;Fish &amp; &quot;chips&quot;&lt;/p&gt;")
+    );
+}
@@ -0,0 +1,130 @@
+//! A minimal HTTP server for testing code that talks to web APIs against
+//! fixtures on the file system.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+/// Serves the files under a root directory over HTTP on a local port.
+///
+/// A request for `/a/b?c=d` is answered with `<root>/a/b` if it exists, or
+/// else `<root>/a/b.json`, `<root>/a/b.html` or `<root>/a/b.xml`, ignoring the
+/// query and method. Anything else is a 404.
+pub struct FixtureServer {
+    address: SocketAddr,
+}
+
+impl FixtureServer {
+    /// Start serving the fixtures in `tests/fixtures/<name>` on a free port.
+    ///
+    /// The server runs until the tests finish.
+    pub fn start(name: &str) -> FixtureServer {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Couldn't bind fixture server");
+        let address = listener.local_addr().expect("Fixture server has no address");
+
+        thread::spawn(move || for stream in listener.incoming() {
+            if let Ok(stream) = stream {
+                serve(&root, stream);
+            }
+        });
+
+        FixtureServer { address }
+    }
+
+    /// The URL of `path` on this server, e.g. `http://127.0.0.1:1234/api`.
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}{}", self.address, path)
+    }
+}
+
+/// The fixture that a request path refers to, if any.
+fn resolve(root: &Path, path: &str) -> Option<PathBuf> {
+    let path = path.split('?').next().unwrap_or("").trim_matches('/');
+
+    if path.split('/').any(|segment| segment == "..") {
+        return None;
+    }
+
+    let exact = root.join(path);
+
+    if exact.is_file() {
+        return Some(exact);
+    }
+
+    ["json", "html", "xml"]
+        .iter()
+        .map(|extension| root.join(format!("{}.{}", path, extension)))
+        .find(|fixture| fixture.is_file())
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => "application/json",
+        Some("html") => "text/html; charset=utf-8",
+        Some("xml") => "application/xml; charset=utf-8",
+        _ => "text/plain; charset=utf-8",
+    }
+}
+
+fn serve(root: &Path, mut stream: TcpStream) {
+    let mut request_line = String::new();
+
+    {
+        let mut reader = BufReader::new(&mut stream);
+
+        if reader.read_line(&mut request_line).is_err() {
+            return;
+        }
+
+        // Skip the headers, and then any body, so that closing the connection
+        // doesn't reset it before the client has read the response.
+        let mut line = String::new();
+        let mut content_length = 0;
+
+        while reader.read_line(&mut line).map(|read| read > 2).unwrap_or(false) {
+            let lowercase = line.to_lowercase();
+
+            if lowercase.starts_with("content-length:") {
+                content_length = lowercase["content-length:".len()..].trim().parse().unwrap_or(0);
+            }
+
+            line.clear();
+        }
+
+        let mut body = vec![0; content_length];
+
+        if reader.read_exact(&mut body).is_err() {
+            return;
+        }
+    }
+
+    let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+
+    let response = match resolve(root, path) {
+        Some(fixture) => {
+            let mut body = vec![];
+
+            File::open(&fixture)
+                .and_then(|mut file| file.read_to_end(&mut body))
+                .expect("Couldn't read fixture");
+
+            let mut response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+                 Connection: close\r\n\r\n",
+                content_type(&fixture),
+                body.len()
+            ).into_bytes();
+
+            response.extend(body);
+            response
+        }
+        None => {
+            b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec()
+        }
+    };
+
+    let _ = stream.write_all(&response);
+}
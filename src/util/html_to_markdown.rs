@@ -1,7 +1,14 @@
+use std::cmp;
+use std::mem;
+
 use html5ever::{QualName, parse_fragment};
 use html5ever::rcdom::{NodeData, RcDom, Handle};
 use html5ever::tendril::TendrilSink;
 
+use slog::Logger;
+
+use util::dom::{attribute, text_content, is_whitespace};
+
 error_chain! {
     errors {
         HtmlParse(errors: Vec<String>) {
@@ -10,7 +17,26 @@ error_chain! {
     }
 }
 
+/// Convert HTML to Markdown, failing if there were any problems parsing it.
+///
+/// The previewers use `convert_logged` instead, since the HTML they get from
+/// APIs is often slightly malformed.
+#[allow(dead_code)]
 pub fn convert(html: &str) -> Result<String> {
+    let conversion = convert_lenient(html);
+
+    if !conversion.warnings.is_empty() {
+        bail!(ErrorKind::HtmlParse(conversion.warnings));
+    }
+
+    Ok(conversion.markdown)
+}
+
+/// Convert HTML to Markdown regardless of any problems parsing it.
+///
+/// The parser recovers from malformed HTML the same way browsers do, so the
+/// result is usually fine. The problems are reported as warnings instead.
+pub fn convert_lenient(html: &str) -> Conversion {
     let mut converter = HtmlVisitor::new();
 
     let context = QualName::new(None, ns!(html), local_name!("body"));
@@ -18,21 +44,118 @@ pub fn convert(html: &str) -> Result<String> {
 
     converter.visit(dom.document);
 
-    if !dom.errors.is_empty() {
-        bail!(ErrorKind::HtmlParse(dom.errors.into_iter().map(|e| e.into_owned()).collect()));
+    Conversion {
+        markdown: converter.into_markdown(),
+        warnings: dom.errors.into_iter().map(|e| e.into_owned()).collect(),
     }
+}
 
-    Ok(converter.into_markdown())
+/// Convert HTML to Markdown with `convert_lenient`, logging any problems
+/// parsing it rather than failing.
+pub fn convert_logged(html: &str, log: &Logger) -> String {
+    let conversion = convert_lenient(html);
+
+    for warning in conversion.warnings() {
+        warn!(log, "Problem parsing HTML"; "warning" => warning.as_str());
+    }
+
+    conversion.into_markdown().trim().to_string()
+}
+
+/// Convert the contents of an already parsed node to Markdown, e.g. one part
+/// of a larger document.
+pub fn convert_node(node: Handle) -> String {
+    let mut converter = HtmlVisitor::new();
+
+    converter.visit_children(node);
+
+    converter.into_markdown()
+}
+
+/// The result of `convert_lenient`.
+#[derive(Clone, Debug)]
+pub struct Conversion {
+    markdown: String,
+    warnings: Vec<String>,
+}
+
+impl Conversion {
+    pub fn markdown(&self) -> &str {
+        &self.markdown
+    }
+
+    /// The problems encountered while parsing the HTML.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    pub fn into_markdown(self) -> String {
+        self.markdown
+    }
+}
+
+/// Escape text so that Discord displays it as-is.
+///
+/// Markdown metacharacters are backslash-escaped, and mass mentions as well as
+/// user, role and channel mentions are broken up with a zero-width space so
+/// that they can't ping anyone.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '\\' | '*' | '_' | '~' | '`' | '>' | '|' | '[' | ']' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+        .replace("@everyone", "@\u{200B}everyone")
+        .replace("@here", "@\u{200B}here")
+        .replace("<@", "<@\u{200B}")
+        .replace("<#", "<#\u{200B}")
+}
+
+/// Percent-encode the characters that would end a Markdown link's href early.
+fn escape_href(href: &str) -> String {
+    href.replace('(', "%28")
+        .replace(')', "%29")
+        .replace(' ', "%20")
+}
+
+/// A fence for a code block that's longer than any run of backticks in the
+/// code, so that the code can't close it early.
+fn code_fence(code: &str) -> String {
+    let longest_run = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+
+    "`".repeat(cmp::max(3, longest_run + 1))
+}
+
+enum List {
+    Unordered,
+    Ordered(usize),
 }
 
 struct HtmlVisitor {
     markdown: String,
+
+    /// Set after a block which has to be followed by a line break, such as a
+    /// code block, so that whatever comes next starts on its own line.
+    needs_newline: bool,
+
+    /// The lists currently being visited, innermost last.
+    lists: Vec<List>,
 }
 
 impl HtmlVisitor {
     fn new() -> HtmlVisitor {
         HtmlVisitor {
             markdown: String::new(),
+            needs_newline: false,
+            lists: vec![],
         }
     }
 
@@ -41,6 +164,56 @@ impl HtmlVisitor {
         self.markdown
     }
 
+    /// Append Markdown to the output.
+    fn push(&mut self, markdown: &str) {
+        if markdown.is_empty() {
+            return;
+        }
+
+        if self.needs_newline {
+            if !markdown.starts_with('\n') {
+                self.markdown.push('\n');
+            }
+
+            self.needs_newline = false;
+        }
+
+        self.markdown.push_str(markdown);
+    }
+
+    /// Make sure that the next output starts on a new line.
+    fn start_line(&mut self) {
+        if !self.markdown.is_empty() && !self.markdown.ends_with('\n') {
+            self.push("\n");
+        }
+    }
+
+    /// Make sure that the next output starts a new paragraph.
+    fn start_paragraph(&mut self) {
+        if self.markdown.is_empty() {
+            return;
+        }
+
+        let trailing = self.markdown.chars().rev().take_while(|&c| c == '\n').count();
+
+        for _ in trailing..2 {
+            self.push("\n");
+        }
+    }
+
+    /// Convert a node's children on their own, without appending them to the
+    /// output.
+    fn render_children(&mut self, node: Handle) -> String {
+        let outer = mem::replace(&mut self.markdown, String::new());
+        let needs_newline = mem::replace(&mut self.needs_newline, false);
+
+        self.visit_children(node);
+
+        self.needs_newline = needs_newline;
+
+        mem::replace(&mut self.markdown, outer)
+    }
+
     fn visit(&mut self, node: Handle) {
         match node.data {
             NodeData::Document => self.visit_children(node.clone()),
@@ -49,8 +222,30 @@ impl HtmlVisitor {
                 match name.local {
                     local_name!("a") => self.visit_link(node.clone()),
                     local_name!("p") => self.visit_paragraph(node.clone()),
+                    local_name!("pre") => self.visit_pre(node.clone()),
                     local_name!("code") => self.visit_code(node.clone()),
-                    local_name!("i") => self.visit_italic(node.clone()),
+                    local_name!("i") | local_name!("em") => self.visit_italic(node.clone()),
+                    local_name!("b") | local_name!("strong") => self.visit_bold(node.clone()),
+                    local_name!("s") | local_name!("strike") | local_name!("del") => {
+                        self.visit_strikethrough(node.clone())
+                    }
+                    local_name!("u") => self.visit_underline(node.clone()),
+                    local_name!("blockquote") => self.visit_blockquote(node.clone()),
+                    local_name!("ul") => self.visit_list(node.clone(), List::Unordered),
+                    local_name!("ol") => {
+                        let start = attribute(&node, "start")
+                            .and_then(|start| start.parse().ok())
+                            .unwrap_or(1);
+
+                        self.visit_list(node.clone(), List::Ordered(start))
+                    }
+                    local_name!("li") => self.visit_list_item(node.clone()),
+                    local_name!("br") => self.push("\n"),
+                    local_name!("h1") | local_name!("h2") | local_name!("h3") |
+                    local_name!("h4") | local_name!("h5") | local_name!("h6") => {
+                        self.visit_heading(node.clone())
+                    }
+                    local_name!("script") | local_name!("style") => {}
                     _ => self.visit_children(node.clone()),
                 }
             }
@@ -66,42 +261,192 @@ impl HtmlVisitor {
         }
     }
 
+    /// Links are written as `[text](href)`, unless the text is just the href
+    /// itself, possibly abbreviated as HN does with long URLs, in which case
+    /// only the href is written.
     fn visit_link(&mut self, node: Handle) {
-        if let NodeData::Element { ref attrs, .. } = node.data {
-            for attr in attrs.borrow().iter() {
-                if &attr.name.local == "href" {
-                    self.markdown.push_str(&*attr.value);
+        let href = attribute(&node, "href");
+        let raw_text = text_content(&node);
+        let text = self.render_children(node);
+
+        match href {
+            Some(href) => {
+                let is_href = raw_text.trim().is_empty() || raw_text == href ||
+                    (raw_text.ends_with("...") &&
+                         href.starts_with(raw_text.trim_right_matches("...")));
+
+                let href = escape_href(&href);
+
+                if is_href {
+                    self.push(&href);
+                } else {
+                    self.push(&format!("[{}]({})", text, href));
                 }
             }
+            None => self.push(&text),
         }
     }
 
+    /// Text is escaped, unlike code, which Discord displays as-is anyway.
     fn visit_text(&mut self, node: Handle) {
         if let NodeData::Text { ref contents } = node.data {
-            self.markdown.push_str(&contents.borrow());
+            self.push(&escape(&contents.borrow()));
         }
     }
 
     fn visit_paragraph(&mut self, node: Handle) {
-        self.markdown.push_str("\n\n");
+        self.start_paragraph();
 
         self.visit_children(node);
     }
 
-    fn visit_code(&mut self, node: Handle) {
-        self.markdown.push_str("```\n");
+    fn visit_heading(&mut self, node: Handle) {
+        self.start_paragraph();
 
+        self.push("**");
         self.visit_children(node);
+        self.push("**");
+
+        self.needs_newline = true;
+    }
+
+    /// Preformatted text becomes a fenced code block, with the language taken
+    /// from a `language-*` class on the inner `code` element if there is one.
+    fn visit_pre(&mut self, node: Handle) {
+        let language = node.children
+            .borrow()
+            .iter()
+            .filter_map(|child| attribute(child, "class"))
+            .flat_map(|class| {
+                class
+                    .split_whitespace()
+                    .filter_map(|class| if class.starts_with("language-") {
+                        Some(class["language-".len()..].to_string())
+                    } else if class.starts_with("lang-") {
+                        Some(class["lang-".len()..].to_string())
+                    } else {
+                        None
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .next()
+            .unwrap_or_else(String::new);
+
+        let mut code = text_content(&node);
+
+        if !code.ends_with('\n') {
+            code.push('\n');
+        }
+
+        self.start_line();
+
+        let fence = code_fence(&code);
+
+        self.push(&format!("{}{}\n{}{}", fence, language, code, fence));
 
-        self.markdown.push_str("```");
+        self.needs_newline = true;
+    }
+
+    /// Code outside of a `pre` is inline code.
+    fn visit_code(&mut self, node: Handle) {
+        let code = text_content(&node);
+
+        if code.contains('`') {
+            self.push(&format!("`` {} ``", code));
+        } else {
+            self.push(&format!("`{}`", code));
+        }
     }
 
     fn visit_italic(&mut self, node: Handle) {
-        self.markdown.push_str("*");
+        self.push("*");
+
+        self.visit_children(node);
+
+        self.push("*");
+    }
+
+    fn visit_bold(&mut self, node: Handle) {
+        self.push("**");
+
+        self.visit_children(node);
+
+        self.push("**");
+    }
+
+    fn visit_strikethrough(&mut self, node: Handle) {
+        self.push("~~");
+
+        self.visit_children(node);
+
+        self.push("~~");
+    }
+
+    fn visit_underline(&mut self, node: Handle) {
+        self.push("__");
 
         self.visit_children(node);
 
-        self.markdown.push_str("*");
+        self.push("__");
+    }
+
+    fn visit_blockquote(&mut self, node: Handle) {
+        let quoted = self.render_children(node);
+
+        self.start_paragraph();
+
+        let lines = quoted
+            .trim()
+            .lines()
+            .map(|line| if line.is_empty() {
+                String::from(">")
+            } else {
+                format!("> {}", line)
+            })
+            .collect::<Vec<_>>();
+
+        self.push(&lines.join("\n"));
+
+        self.needs_newline = true;
+    }
+
+    fn visit_list(&mut self, node: Handle, list: List) {
+        self.lists.push(list);
+
+        for child in node.children.borrow().iter() {
+            if !is_whitespace(child) {
+                self.visit(child.clone());
+            }
+        }
+
+        self.lists.pop();
+
+        self.needs_newline = true;
+    }
+
+    fn visit_list_item(&mut self, node: Handle) {
+        let depth = self.lists.len();
+
+        let marker = match self.lists.last_mut() {
+            Some(&mut List::Ordered(ref mut number)) => {
+                *number += 1;
+
+                format!("{}. ", *number - 1)
+            }
+            Some(&mut List::Unordered) | None => String::from("- "),
+        };
+
+        self.start_line();
+
+        let indent = "  ".repeat(depth.saturating_sub(1));
+
+        self.push(&format!("{}{}", indent, marker));
+
+        for child in node.children.borrow().iter() {
+            if !is_whitespace(child) {
+                self.visit(child.clone());
+            }
+        }
     }
 }
 
@@ -131,3 +476,99 @@ fn test_html_to_markdown() {
 
     assert_eq!(expected, parsed);
 }
+
+#[test]
+fn test_html_to_markdown_constructs() {
+    use std::path::Path;
+    use std::fs::File;
+    use std::io::Read;
+
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/html_to_markdown/");
+
+    let constructs = [
+        "link",
+        "inline_code",
+        "code_block",
+        "bold",
+        "italic",
+        "strikethrough",
+        "blockquote",
+        "unordered_list",
+        "ordered_list",
+        "nested_list",
+        "line_break",
+        "escaping",
+    ];
+
+    for construct in constructs.iter() {
+        let mut input = String::new();
+        File::open(fixtures.join(format!("{}.html", construct)))
+            .and_then(|mut file| file.read_to_string(&mut input))
+            .expect("Couldn't read fixture");
+
+        let mut expected = String::new();
+        File::open(fixtures.join(format!("{}.markdown", construct)))
+            .and_then(|mut file| file.read_to_string(&mut expected))
+            .expect("Couldn't read fixture");
+
+        let parsed = convert(&input).expect("Couldn't convert HTML to Markdown");
+
+        assert_eq!(expected, parsed, "converting {}", construct);
+    }
+}
+
+#[test]
+fn test_convert_strict() {
+    let html = "Some text with a stray </i> tag";
+
+    match convert(html) {
+        Err(Error(ErrorKind::HtmlParse(ref errors), _)) => assert!(!errors.is_empty()),
+        other => panic!("Expected an HTML parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_convert_lenient() {
+    let html = "Some text with a stray </i> tag";
+
+    let conversion = convert_lenient(html);
+
+    assert_eq!("Some text with a stray  tag", conversion.markdown());
+    assert!(!conversion.warnings().is_empty());
+}
+
+#[test]
+fn test_link_href_escaping() {
+    let html = concat!(
+        r#"See <a href="https://en.wikipedia.org/wiki/Rust_(programming language)">"#,
+        "Rust</a>"
+    );
+
+    assert_eq!(
+        "See [Rust](https://en.wikipedia.org/wiki/Rust_%28programming%20language%29)",
+        convert_lenient(html).into_markdown()
+    );
+}
+
+#[test]
+fn test_code_block_fence() {
+    let html = "<pre><code>let md = \"```rust\";</code></pre>";
+
+    assert_eq!("````\nlet md = \"```rust\";\n````", convert_lenient(html).into_markdown());
+}
+
+#[test]
+fn test_escape() {
+    assert_eq!(r"\*args and \_\_init\_\_", escape("*args and __init__"));
+    assert_eq!(r"\~\~no\~\~ \`tick\` \> \| a\\b", escape("~~no~~ `tick` > | a\\b"));
+    assert_eq!(
+        "@\u{200B}everyone and @\u{200B}here",
+        escape("@everyone and @here")
+    );
+    assert_eq!(
+        "<@\u{200B}123\\> <@\u{200B}!4\\> <@\u{200B}&5\\> <#\u{200B}6\\>",
+        escape("<@123> <@!4> <@&5> <#6>")
+    );
+    assert_eq!(r"\[not\](a link)", escape("[not](a link)"));
+    assert_eq!("user@example.com", escape("user@example.com"));
+}
@@ -0,0 +1,370 @@
+//! Fetching arbitrary pages without trusting them to be small or quick.
+
+use std::cmp;
+use std::io::{self, Read};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use reqwest::{self, RedirectPolicy, Response};
+use reqwest::header::{ContentType, UserAgent};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json;
+use url::{self, Url, Host};
+
+error_chain! {
+    foreign_links {
+        Io(io::Error);
+        Reqwest(reqwest::Error);
+        Json(serde_json::Error);
+        Url(url::ParseError);
+    }
+
+    errors {
+        UnsuccessfulStatus(status: u16) {
+            description("The server responded with an unsuccessful status")
+            display("The server responded with status {}", status)
+        }
+        LocalAddress(url: String) {
+            description("The URL is on the local machine or network")
+            display("Refusing to fetch {}, which is on the local machine or network", url)
+        }
+        TimedOut(timeout: Duration) {
+            description("The request took too long")
+            display("The request took longer than {} seconds", timeout.as_secs())
+        }
+    }
+}
+
+/// The user agent sent with every request, since some sites refuse requests
+/// without one.
+pub const USER_AGENT: &'static str = concat!("bmo/", env!("CARGO_PKG_VERSION"));
+
+/// The most redirects followed for a single request.
+pub const MAX_REDIRECTS: usize = 10;
+
+/// Limits on how much a single request may cost, and where it may go.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    /// How long to wait for the whole request, including redirects.
+    ///
+    /// Each read from the server is given this long too, and the deadline is
+    /// only checked between them, so a request can overrun it by up to one
+    /// slow read.
+    pub timeout: Duration,
+
+    /// The most bytes of the body to read. Anything beyond this is ignored.
+    pub max_size: u64,
+
+    /// Whether to refuse URLs on the local machine or network, including
+    /// any redirected to, and hosts whose names resolve to such addresses.
+    /// URLs posted in chat shouldn't be able to make us fetch those.
+    ///
+    /// Names are resolved separately from the request itself, so this
+    /// doesn't stop a name that resolves to a public address for the check
+    /// and a local one for the request, i.e. DNS rebinding.
+    pub public_only: bool,
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            timeout: Duration::from_secs(5),
+            max_size: 512 * 1024,
+            public_only: false,
+        }
+    }
+}
+
+/// Whether an IP address is on the local machine or network.
+pub fn is_local_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_local_ipv4(ip),
+        IpAddr::V6(ip) => {
+            let segments = ip.segments();
+
+            // e.g. ::ffff:127.0.0.1
+            let is_ipv4_mapped = segments[..5].iter().all(|&segment| segment == 0) &&
+                segments[5] == 0xFFFF;
+
+            if is_ipv4_mapped {
+                return is_local_ipv4(Ipv4Addr::new(
+                    (segments[6] >> 8) as u8,
+                    segments[6] as u8,
+                    (segments[7] >> 8) as u8,
+                    segments[7] as u8,
+                ));
+            }
+
+            ip.is_loopback() || ip.is_unspecified() || is_local_ipv6(&ip)
+        }
+    }
+}
+
+fn is_local_ipv4(ip: Ipv4Addr) -> bool {
+    ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified() ||
+        ip.is_broadcast()
+}
+
+/// Whether an IPv6 address is unique local (fc00::/7) or link-local
+/// (fe80::/10).
+fn is_local_ipv6(ip: &Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+
+    first & 0xFE00 == 0xFC00 || first & 0xFFC0 == 0xFE80
+}
+
+/// Whether a host is on the local machine or network, going by its name or
+/// address alone.
+pub fn is_local_host(host: Host<&str>) -> bool {
+    match host {
+        Host::Domain(domain) => domain == "localhost" || domain.ends_with(".localhost"),
+        Host::Ipv4(ip) => is_local_ip(IpAddr::V4(ip)),
+        Host::Ipv6(ip) => is_local_ip(IpAddr::V6(ip)),
+    }
+}
+
+/// Whether a URL is on a public host, once its name is resolved.
+///
+/// Names which can't be resolved aren't public, since they can't be fetched
+/// anyway. The request resolves the name again, which may give a different
+/// answer, so this is a safeguard against mistakes rather than against a
+/// hostile name server.
+fn is_public(url: &Url) -> bool {
+    let host = match url.host() {
+        Some(host) => host,
+        None => return false,
+    };
+
+    if is_local_host(host.clone()) {
+        return false;
+    }
+
+    match host {
+        Host::Domain(domain) => {
+            let port = url.port_or_known_default().unwrap_or(80);
+
+            match (domain, port).to_socket_addrs() {
+                Ok(addresses) => {
+                    let addresses = addresses.collect::<Vec<_>>();
+
+                    !addresses.is_empty() &&
+                        addresses.iter().all(|address| !is_local_ip(address.ip()))
+                }
+                Err(_) => false,
+            }
+        }
+        Host::Ipv4(_) | Host::Ipv6(_) => true,
+    }
+}
+
+/// A fetched page.
+#[derive(Clone, Debug)]
+pub struct Page {
+    url: Url,
+    body: String,
+}
+
+impl Page {
+    /// The URL the page was ultimately fetched from, after any redirects.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// The body of the page, cut off at the size limit.
+    ///
+    /// Invalid UTF-8, including a character split by the size limit, is
+    /// replaced rather than rejected.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+}
+
+/// GET a URL, giving up once `limits` are exceeded.
+pub fn get(url: &str, limits: Limits) -> Result<Page> {
+    let started = Instant::now();
+
+    read(send(url, limits)?, limits, started)
+}
+
+/// GET a URL and parse the response as JSON.
+///
+/// A response larger than the size limit fails to parse, since it's cut off.
+pub fn get_json<T: DeserializeOwned>(url: &str, limits: Limits) -> Result<T> {
+    let started = Instant::now();
+
+    read_json(send(url, limits)?, limits, started)
+}
+
+/// GET an HTML page, giving up once `limits` are exceeded.
+///
+/// Anything other than HTML is `None`, without its body ever being read.
+pub fn get_html(url: &str, limits: Limits) -> Result<Option<Page>> {
+    let started = Instant::now();
+    let response = send(url, limits)?;
+
+    let is_html = response.headers().get::<ContentType>().map_or(false, |content_type| {
+        let content_type = content_type.to_string();
+
+        content_type.starts_with("text/html") ||
+            content_type.starts_with("application/xhtml+xml")
+    });
+
+    if is_html {
+        Ok(Some(read(response, limits, started)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// POST `body` to a URL as JSON, and parse the response as JSON.
+///
+/// A response larger than the size limit fails to parse, since it's cut off.
+pub fn post_json<B, T>(url: &str, body: &B, limits: Limits) -> Result<T>
+where
+    B: Serialize,
+    T: DeserializeOwned,
+{
+    let started = Instant::now();
+
+    if limits.public_only && !is_public(&Url::parse(url)?) {
+        bail!(ErrorKind::LocalAddress(url.to_string()));
+    }
+
+    let response = client(limits)?
+        .post(url)?
+        .header(UserAgent::new(USER_AGENT))
+        .header(ContentType::json())
+        .body(serde_json::to_vec(body)?)
+        .send()?;
+
+    read_json(check(response)?, limits, started)
+}
+
+fn client(limits: Limits) -> Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder()?;
+
+    builder.timeout(limits.timeout);
+
+    if limits.public_only {
+        builder.redirect(RedirectPolicy::custom(|attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                attempt.too_many_redirects()
+            } else if is_public(attempt.url()) {
+                attempt.follow()
+            } else {
+                // The redirect itself is then the response, which fails as
+                // an unsuccessful status.
+                attempt.stop()
+            }
+        }));
+    }
+
+    Ok(builder.build()?)
+}
+
+fn send(url: &str, limits: Limits) -> Result<Response> {
+    if limits.public_only && !is_public(&Url::parse(url)?) {
+        bail!(ErrorKind::LocalAddress(url.to_string()));
+    }
+
+    let response = client(limits)?
+        .get(url)?
+        .header(UserAgent::new(USER_AGENT))
+        .send()?;
+
+    check(response)
+}
+
+fn check(response: Response) -> Result<Response> {
+    if !response.status().is_success() {
+        bail!(ErrorKind::UnsuccessfulStatus(response.status().as_u16()));
+    }
+
+    Ok(response)
+}
+
+/// Read a response's body up to the size limit, failing if the request
+/// started more than the timeout ago.
+fn read_body(mut response: Response, limits: Limits, started: Instant) -> Result<Vec<u8>> {
+    let mut body = vec![];
+    let mut chunk = [0; 8 * 1024];
+
+    while (body.len() as u64) < limits.max_size {
+        if started.elapsed() > limits.timeout {
+            bail!(ErrorKind::TimedOut(limits.timeout));
+        }
+
+        let wanted = cmp::min(chunk.len() as u64, limits.max_size - body.len() as u64) as usize;
+        let read = response.read(&mut chunk[..wanted])?;
+
+        if read == 0 {
+            break;
+        }
+
+        body.extend_from_slice(&chunk[..read]);
+    }
+
+    Ok(body)
+}
+
+fn read(response: Response, limits: Limits, started: Instant) -> Result<Page> {
+    let url = response.url().clone();
+    let body = read_body(response, limits, started)?;
+
+    Ok(Page {
+        url,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn read_json<T: DeserializeOwned>(
+    response: Response,
+    limits: Limits,
+    started: Instant,
+) -> Result<T> {
+    let body = read_body(response, limits, started)?;
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+#[test]
+fn test_is_local_ip() {
+    let cases = vec![
+        ("127.0.0.1", true),
+        ("10.1.2.3", true),
+        ("172.16.0.1", true),
+        ("192.168.1.1", true),
+        ("169.254.169.254", true),
+        ("0.0.0.0", true),
+        ("93.184.216.34", false),
+        ("::1", true),
+        ("::", true),
+        ("fc00::1", true),
+        ("fd12:3456::1", true),
+        ("fe80::1", true),
+        ("::ffff:127.0.0.1", true),
+        ("::ffff:10.0.0.1", true),
+        ("::ffff:93.184.216.34", false),
+        ("2606:2800:220:1:248:1893:25c8:1946", false),
+    ];
+
+    for (ip, expected) in cases {
+        assert_eq!(expected, is_local_ip(ip.parse().unwrap()), "checking {}", ip);
+    }
+}
+
+#[test]
+fn test_public_only() {
+    let limits = Limits {
+        public_only: true,
+        ..Limits::default()
+    };
+
+    for url in &["http://127.0.0.1:1/", "http://localhost/", "http://[::ffff:10.0.0.1]/"] {
+        match get(url, limits) {
+            Err(Error(ErrorKind::LocalAddress(_), _)) => {}
+            result => panic!("Expected {} to be refused, got {:?}", url, result),
+        }
+    }
+}
@@ -1 +1,27 @@
+pub mod dom;
+#[cfg(test)]
+pub mod fixture_server;
+pub mod http;
 pub mod html_to_markdown;
+#[cfg(test)]
+pub mod preview_fixture;
+pub mod truncate;
+
+use std::fmt::Display;
+
+/// Format a count in bold, followed by a noun that's pluralised with an "s"
+/// unless the count is one, e.g. "**3** comments".
+pub fn plural<T: Display + PartialEq + From<u8>>(count: T, singular: &str) -> String {
+    if count == T::from(1) {
+        format!("**{}** {}", count, singular)
+    } else {
+        format!("**{}** {}s", count, singular)
+    }
+}
+
+#[test]
+fn test_plural() {
+    assert_eq!("**1** point", plural(1i64, "point"));
+    assert_eq!("**0** comments", plural(0u64, "comment"));
+    assert_eq!("**-2** points", plural(-2i64, "point"));
+}
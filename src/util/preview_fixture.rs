@@ -0,0 +1,30 @@
+//! Previewing fixture URLs in tests.
+
+use slog::{Logger, Discard};
+use url::Url;
+
+use errors::Result;
+use preview::{Preview, Card};
+
+/// Preview a URL with a previewer, which must match it.
+///
+/// The previewer is expected to talk to a `FixtureServer` rather than the
+/// real site.
+pub fn try_preview_fixture(previewer: &Preview, url: &str) -> Result<Option<Card>> {
+    let log = Logger::root(Discard, o!());
+    let url = Url::parse(url).unwrap();
+
+    assert!(previewer.matches(&url), "{} doesn't match {}", previewer.name(), url);
+
+    previewer.preview(&url, &log)
+}
+
+/// Preview a URL like `try_preview_fixture`, failing the test unless there's
+/// a card.
+pub fn preview_fixture(previewer: &Preview, url: &str) -> Card {
+    match try_preview_fixture(previewer, url) {
+        Ok(Some(card)) => card,
+        Ok(None) => panic!("{} had nothing to preview for {}", previewer.name(), url),
+        Err(e) => panic!("{} couldn't preview {}: {}", previewer.name(), url, e),
+    }
+}
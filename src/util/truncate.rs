@@ -0,0 +1,456 @@
+//! Shortening Markdown to fit within Discord's length limits.
+//!
+//! Markdown is only ever cut at whitespace, or at line breaks within code
+//! blocks, so that links, inline code and escapes are never split. Any
+//! formatting that's open at the cut is closed, and when splitting, reopened
+//! at the start of the next chunk.
+
+/// A place where Markdown can be cut without breaking it.
+struct Cut {
+    /// The byte index at which to cut.
+    index: usize,
+
+    /// The number of characters before `index`.
+    chars: usize,
+
+    /// What needs to be appended to the Markdown before the cut to close any
+    /// open formatting.
+    closing: String,
+
+    /// What needs to be prepended to the Markdown after the cut to reopen the
+    /// formatting that was closed.
+    reopening: String,
+
+    /// Whether the cut is within a code block, where whitespace is
+    /// significant.
+    fenced: bool,
+}
+
+/// The length of the link starting at the beginning of `markdown`, if any.
+///
+/// The link text ends at the `]` that closes the opening `[`, which has to be
+/// followed directly by the `(` of the href.
+fn link_length(markdown: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut escaped = false;
+    let mut text_end = None;
+
+    for (index, c) in markdown.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '\n' => return None,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+
+                if depth == 0 {
+                    text_end = Some(index);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let text_end = match text_end {
+        Some(index) if markdown[index + 1..].starts_with('(') => index,
+        _ => return None,
+    };
+
+    markdown[text_end + 2..]
+        .find(')')
+        .map(|href_end| text_end + 2 + href_end + 1)
+}
+
+/// The length of the inline code span starting at the beginning of
+/// `markdown`, if any.
+fn code_span_length(markdown: &str) -> Option<usize> {
+    let ticks = markdown.chars().take_while(|&c| c == '`').count();
+    let delimiter = "`".repeat(ticks);
+
+    markdown[ticks..]
+        .find(&delimiter)
+        .map(|end| ticks + end + ticks)
+}
+
+/// Close `marker` if it's the innermost open formatting, or else open it.
+fn toggle(markers: &mut Vec<&'static str>, marker: &'static str) {
+    if markers.last() == Some(&marker) {
+        markers.pop();
+    } else {
+        markers.push(marker);
+    }
+}
+
+/// Find every place where the Markdown can be safely cut.
+fn cuts(markdown: &str) -> Vec<Cut> {
+    let mut cuts = vec![];
+
+    let mut markers: Vec<&'static str> = vec![];
+
+    // The line opening the code block we're in, if any, and how many
+    // backticks its fence has.
+    let mut fence: Option<(String, usize)> = None;
+
+    let mut index = 0;
+    let mut chars = 0;
+
+    while index < markdown.len() {
+        let rest = &markdown[index..];
+        let c = rest.chars().next().expect("index is within the string");
+
+        let line_start = index == 0 || markdown[..index].ends_with('\n');
+        let ticks = rest.chars().take_while(|&c| c == '`').count();
+
+        let length = if let Some((opener, fence_ticks)) = fence.clone() {
+            // Only a fence at least as long as the opening one closes the
+            // block, so shorter ones can appear within it.
+            if line_start && ticks >= fence_ticks {
+                fence = None;
+
+                ticks
+            } else {
+                if c == '\n' {
+                    cuts.push(Cut {
+                        index: index + 1,
+                        chars: chars + 1,
+                        closing: "`".repeat(fence_ticks),
+                        reopening: format!("{}\n", opener),
+                        fenced: true,
+                    });
+                }
+
+                c.len_utf8()
+            }
+        } else if ticks >= 3 {
+            let line_end = rest.find('\n').map(|end| end + 1).unwrap_or(rest.len());
+
+            fence = Some((rest[..line_end].trim_right().to_string(), ticks));
+
+            line_end
+        } else {
+            match c {
+                '\\' => {
+                    c.len_utf8() + rest[1..].chars().next().map(char::len_utf8).unwrap_or(0)
+                }
+                '`' => code_span_length(rest).unwrap_or(1),
+                '[' => link_length(rest).unwrap_or(1),
+                '*' if rest.starts_with("***") => {
+                    // Bold and italic at once, closing whichever is innermost
+                    // first.
+                    if markers.last() == Some(&"*") {
+                        toggle(&mut markers, "*");
+                        toggle(&mut markers, "**");
+                    } else {
+                        toggle(&mut markers, "**");
+                        toggle(&mut markers, "*");
+                    }
+
+                    3
+                }
+                '*' | '_' | '~' if rest.starts_with("**") || rest.starts_with("__") ||
+                                   rest.starts_with("~~") => {
+                    let marker = match c {
+                        '*' => "**",
+                        '_' => "__",
+                        _ => "~~",
+                    };
+
+                    toggle(&mut markers, marker);
+
+                    2
+                }
+                '*' => {
+                    toggle(&mut markers, "*");
+
+                    1
+                }
+                c if c.is_whitespace() => {
+                    cuts.push(Cut {
+                        index,
+                        chars,
+                        closing: markers.iter().rev().cloned().collect(),
+                        reopening: markers.iter().cloned().collect(),
+                        fenced: false,
+                    });
+
+                    c.len_utf8()
+                }
+                c => c.len_utf8(),
+            }
+        };
+
+        chars += rest[..length].chars().count();
+        index += length;
+    }
+
+    cuts
+}
+
+/// The byte index of the `n`th character, or the end of the string.
+fn char_index(text: &str, n: usize) -> usize {
+    text.char_indices()
+        .nth(n)
+        .map(|(index, _)| index)
+        .unwrap_or(text.len())
+}
+
+/// Truncate Markdown to at most `limit` characters.
+///
+/// If anything is cut, an ellipsis is appended, followed by a link to
+/// `read_more` if given. If there's no safe place to cut, e.g. because the
+/// Markdown is a single huge link, it's cut at the limit regardless.
+pub fn truncate(markdown: &str, limit: usize, read_more: Option<&str>) -> String {
+    if markdown.chars().count() <= limit {
+        return markdown.to_string();
+    }
+
+    let ellipsis = match read_more {
+        // Angle brackets keep Discord from embedding the link.
+        Some(url) => format!("… Read more: <{}>", url),
+        None => String::from("…"),
+    };
+
+    let ellipsis_length = ellipsis.chars().count();
+
+    let cut = cuts(markdown).into_iter().rev().find(|cut| {
+        // Fenced cuts put the ellipsis on a line of its own.
+        let newline = if cut.fenced { 1 } else { 0 };
+
+        cut.chars + cut.closing.chars().count() + newline + ellipsis_length <= limit
+    });
+
+    match cut {
+        Some(ref cut) if cut.fenced => {
+            format!("{}{}\n{}", &markdown[..cut.index], cut.closing, ellipsis)
+        }
+        Some(ref cut) => {
+            format!(
+                "{}{}{}",
+                markdown[..cut.index].trim_right(),
+                cut.closing,
+                ellipsis
+            )
+        }
+        None => {
+            let end = char_index(markdown, limit.saturating_sub(ellipsis_length));
+
+            format!("{}{}", &markdown[..end], ellipsis)
+        }
+    }
+}
+
+/// Split Markdown into chunks of at most `limit` characters each, e.g. to
+/// send it as several messages.
+///
+/// As with `truncate`, chunks are only cut at safe places unless there are
+/// none.
+pub fn split(markdown: &str, limit: usize) -> Vec<String> {
+    let mut chunks = vec![];
+    let mut rest = markdown.to_string();
+
+    while rest.chars().count() > limit {
+        // Only consider cuts that make progress, i.e. those that leave less
+        // behind than there was to begin with once formatting is reopened.
+        let cut = cuts(&rest).into_iter().rev().find(|cut| {
+            cut.index > cut.reopening.len() && cut.chars + cut.closing.chars().count() <= limit
+        });
+
+        let (chunk, next) = match cut {
+            Some(ref cut) if cut.fenced => {
+                (
+                    format!("{}{}", &rest[..cut.index], cut.closing),
+                    format!("{}{}", cut.reopening, &rest[cut.index..]),
+                )
+            }
+            Some(ref cut) => {
+                (
+                    format!("{}{}", rest[..cut.index].trim_right(), cut.closing),
+                    format!("{}{}", cut.reopening, rest[cut.index..].trim_left()),
+                )
+            }
+            None => {
+                let end = char_index(&rest, limit);
+
+                (rest[..end].to_string(), rest[end..].to_string())
+            }
+        };
+
+        chunks.push(chunk);
+        rest = next;
+    }
+
+    if !rest.trim().is_empty() {
+        chunks.push(rest);
+    }
+
+    chunks
+}
+
+/// The most characters of source code shown in a preview, leaving room in
+/// its message for the fences around it.
+pub const CODE_LENGTH: usize = 1900;
+
+/// The lines kept from the start of some source code.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Excerpt {
+    /// The lines, joined with newlines.
+    pub source: String,
+
+    /// How many lines were kept, including any cut partway through.
+    pub lines: usize,
+
+    /// Whether the only line kept was cut partway through, since it alone was
+    /// over the character limit.
+    pub cut_line: bool,
+}
+
+/// Keep as many whole lines from the start of `lines` as fit within
+/// `max_lines` lines and `max_chars` characters.
+///
+/// Unlike Markdown, code is only ever cut at line breaks, unless the first
+/// line alone is too long.
+pub fn excerpt(lines: &[&str], max_lines: usize, max_chars: usize) -> Excerpt {
+    let mut source = String::new();
+    let mut chars = 0;
+    let mut kept = 0;
+
+    for line in lines.iter().take(max_lines) {
+        let separator = if kept == 0 { 0 } else { 1 };
+        let length = line.chars().count();
+
+        if chars + separator + length > max_chars {
+            if kept == 0 {
+                return Excerpt {
+                    source: line[..char_index(line, max_chars)].to_string(),
+                    lines: 1,
+                    cut_line: true,
+                };
+            }
+
+            break;
+        }
+
+        if separator > 0 {
+            source.push('\n');
+        }
+
+        source.push_str(line);
+        chars += separator + length;
+        kept += 1;
+    }
+
+    Excerpt {
+        source,
+        lines: kept,
+        cut_line: false,
+    }
+}
+
+#[test]
+fn test_truncate_short() {
+    assert_eq!("short", truncate("short", 10, None));
+}
+
+#[test]
+fn test_truncate_words() {
+    assert_eq!("a bit…", truncate("a bit longer", 8, None));
+    assert_eq!(
+        "a… Read more: <https://x.y>",
+        truncate("a bit longer than that, really", 29, Some("https://x.y"))
+    );
+}
+
+#[test]
+fn test_truncate_closes_formatting() {
+    assert_eq!("some **bold ~~and~~**…", truncate("some **bold ~~and~~ more**", 24, None));
+    assert_eq!("*one*…", truncate("*one two*", 8, None));
+    assert_eq!("***bold***…", truncate("***bold italic*** rest", 16, None));
+    assert_eq!("***bold italic***…", truncate("***bold italic*** rest", 20, None));
+}
+
+#[test]
+fn test_truncate_never_splits_links() {
+    assert_eq!(
+        "see…",
+        truncate("see [the docs](https://example.com/a b) now", 30, None)
+    );
+}
+
+#[test]
+fn test_link_length() {
+    assert_eq!(Some(6), link_length("[x](y) z"));
+    assert_eq!(Some(10), link_length("[a [b]](c)"));
+    assert_eq!(Some(8), link_length("[a\\]](c)"));
+    assert_eq!(None, link_length("[1] see [x](y)"));
+    assert_eq!(None, link_length("[a\nb](c)"));
+
+    // Cuts within text that merely precedes a link are still found.
+    assert_eq!("[1]…", truncate("[1] see [the docs](https://e.com) now", 6, None));
+}
+
+#[test]
+fn test_truncate_code_block() {
+    assert_eq!(
+        "code:\n```rust\nlet a = 1;\n```\n…",
+        truncate("code:\n```rust\nlet a = 1;\nlet b = 2;\n```", 32, None)
+    );
+
+    // The newline before the ellipsis counts towards the limit too.
+    assert_eq!(
+        "code:…",
+        truncate("code:\n```rust\nlet a = 1;\nlet b = 2;\n```", 29, None)
+    );
+
+    // A shorter fence within the block doesn't close it.
+    assert_eq!(
+        "x\n````\na\n```\n````\n…",
+        truncate("x\n````\na\n```\nbbbb\n````", 20, None)
+    );
+}
+
+#[test]
+fn test_truncate_without_safe_cut() {
+    assert_eq!("ünï…", truncate("ünïcodeünïcode", 4, None));
+}
+
+#[test]
+fn test_split() {
+    assert_eq!(
+        vec!["one **two**", "**three** four"],
+        split("one **two three** four", 14)
+    );
+    assert_eq!(
+        vec!["```\na\n```", "```\nb\n```"],
+        split("```\na\nb\n```", 10)
+    );
+    assert_eq!(
+        vec!["````\na\n```\n````", "````\nb\n````"],
+        split("````\na\n```\nb\n````", 15)
+    );
+}
+
+#[test]
+fn test_excerpt() {
+    let lines = ["one", "two", "three", "four"];
+
+    let keep = |max_lines, max_chars| {
+        let kept = excerpt(&lines, max_lines, max_chars);
+
+        (kept.source, kept.lines, kept.cut_line)
+    };
+
+    assert_eq!(("one\ntwo\nthree\nfour".to_string(), 4, false), keep(10, 100));
+    assert_eq!(("one\ntwo".to_string(), 2, false), keep(2, 100));
+    assert_eq!(("one\ntwo".to_string(), 2, false), keep(10, 12));
+    assert_eq!(("on".to_string(), 1, true), keep(10, 2));
+    assert_eq!(
+        Excerpt {
+            source: String::new(),
+            lines: 0,
+            cut_line: false,
+        },
+        excerpt(&[], 10, 100)
+    );
+}
@@ -0,0 +1,73 @@
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+
+use util::http::{self, Limits};
+
+use super::{ErrorKind, Result};
+
+use super::section::Sections;
+use super::summary::Summary;
+
+/// The base URL of the REST API of each Wikipedia, where `{language}` is
+/// replaced with the language subdomain.
+pub const API_BASE_URL: &'static str = "https://{language}.wikipedia.org/api/rest_v1";
+
+/// Talks to the REST API of each language's Wikipedia.
+pub struct Client {
+    base_url: String,
+    limits: Limits,
+}
+
+impl Client {
+    pub fn new() -> Client {
+        Client::with_base_url(API_BASE_URL)
+    }
+
+    /// Create a client which talks to APIs rooted at `base_url` instead of
+    /// Wikipedia's, where `{language}` is replaced with the language, e.g.
+    /// `http://localhost:8080/{language}`.
+    pub fn with_base_url<S: Into<String>>(base_url: S) -> Client {
+        Client {
+            base_url: base_url.into().trim_right_matches('/').to_string(),
+            limits: Limits::default(),
+        }
+    }
+
+    fn url(&self, language: &str, endpoint: &str, title: &str) -> String {
+        let title = title.replace(' ', "_");
+
+        format!(
+            "{}/page/{}/{}",
+            self.base_url.replace("{language}", language),
+            endpoint,
+            utf8_percent_encode(&title, PATH_SEGMENT_ENCODE_SET)
+        )
+    }
+
+    pub fn get_summary(&self, language: &str, title: &str) -> Result<Summary> {
+        let url = self.url(language, "summary", title);
+
+        Ok(http::get_json(&url, self.limits)?)
+    }
+
+    /// Fetch the sections of an article from its HTML.
+    ///
+    /// Articles larger than the size limit are cut off, so the sections
+    /// towards the end of them can be missing.
+    pub fn get_sections(&self, language: &str, title: &str) -> Result<Sections> {
+        let url = self.url(language, "html", title);
+
+        match http::get_html(&url, self.limits)? {
+            Some(page) => Ok(Sections::parse(page.body())),
+            None => bail!(ErrorKind::NotHtml(url)),
+        }
+    }
+}
+
+/// A `Client` talking to a server for the fixtures in
+/// `tests/fixtures/wikipedia`, with each language's under its own directory.
+#[cfg(test)]
+pub fn fixtures() -> Client {
+    use util::fixture_server::FixtureServer;
+
+    Client::with_base_url(FixtureServer::start("wikipedia").url("/{language}"))
+}
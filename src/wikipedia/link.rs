@@ -0,0 +1,160 @@
+use url::Url;
+use url::percent_encoding::percent_decode;
+
+/// The Wikipedia article, and possibly section, that a URL refers to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Target {
+    /// The language subdomain of the Wikipedia, e.g. `en`.
+    pub language: String,
+
+    /// The title of the article, with spaces rather than underscores.
+    pub title: String,
+
+    /// The title of the section, with spaces rather than underscores.
+    ///
+    /// Any escapes with dots rather than percent signs, which older links
+    /// have, are left as they are, since they can't be told apart from dots
+    /// in the title itself without the article's sections.
+    pub section: Option<String>,
+}
+
+/// Decode a title or section as it appears in a URL, e.g. `Caf%C3%A9_au_lait`
+/// becomes `Café au lait`.
+fn decode(encoded: &str) -> String {
+    percent_decode(encoded.as_bytes()).decode_utf8_lossy().replace('_', " ")
+}
+
+/// Decode the escapes in a section as older MediaWiki wrote them, with dots
+/// rather than percent signs, e.g. `caf.C3.A9` becomes `café`.
+///
+/// Only escapes of characters MediaWiki would have escaped are decoded, and
+/// only if they decode to valid UTF-8, but dots are common in section titles
+/// by themselves, so the result is only a guess.
+pub fn decode_dots(section: &str) -> String {
+    let bytes = section.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let is_escape = bytes[i] == b'.' && i + 2 < bytes.len() &&
+            (bytes[i + 1] as char).is_digit(16) &&
+            (bytes[i + 2] as char).is_digit(16);
+
+        let escaped = if is_escape {
+            u8::from_str_radix(&section[i + 1..i + 3], 16).ok().and_then(|byte| {
+                let is_plain = (byte as char).is_digit(36) || byte < 0x20 || byte == 0x7F;
+
+                if is_plain { None } else { Some(byte) }
+            })
+        } else {
+            None
+        };
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8(decoded).unwrap_or_else(|_| section.to_string())
+}
+
+/// Figure out which article a URL refers to, if any.
+///
+/// This understands every language's Wikipedia, including the mobile site.
+pub fn recognize(url: &Url) -> Option<Target> {
+    let host = match url.host_str() {
+        Some(host) => host,
+        None => return None,
+    };
+
+    // e.g. en.wikipedia.org or en.m.wikipedia.org
+    let language = match host.trim_right_matches(".wikipedia.org") {
+        subdomain if subdomain == host => return None,
+        subdomain => subdomain.trim_right_matches(".m"),
+    };
+
+    if language.is_empty() || language == "www" || language == "m" || language.contains('.') {
+        return None;
+    }
+
+    let title = if url.path().starts_with("/wiki/") {
+        // Titles can contain slashes, e.g. /wiki/AC/DC
+        decode(&url.path()["/wiki/".len()..])
+    } else if url.path() == "/w/index.php" {
+        match url.query_pairs().find(|param| param.0 == "title") {
+            Some((_, title)) => title.replace('_', " "),
+            None => return None,
+        }
+    } else {
+        return None;
+    };
+
+    // Special pages such as Special:Random aren't articles.
+    if title.trim().is_empty() || title.starts_with("Special:") {
+        return None;
+    }
+
+    let section = url.fragment()
+        .map(decode)
+        .and_then(|section| if section.is_empty() { None } else { Some(section) });
+
+    Some(Target {
+        language: language.to_string(),
+        title,
+        section,
+    })
+}
+
+#[test]
+fn test_recognize() {
+    fn target(language: &str, title: &str, section: Option<&str>) -> Option<Target> {
+        Some(Target {
+            language: String::from(language),
+            title: String::from(title),
+            section: section.map(String::from),
+        })
+    }
+
+    let cases = vec![
+        ("https://en.wikipedia.org/wiki/Rust_(programming_language)",
+         target("en", "Rust (programming language)", None)),
+        ("https://en.m.wikipedia.org/wiki/Rust_(programming_language)#History",
+         target("en", "Rust (programming language)", Some("History"))),
+        ("https://fr.wikipedia.org/wiki/Caf%C3%A9#Histoire_du_caf%C3%A9",
+         target("fr", "Café", Some("Histoire du café"))),
+        ("https://fr.wikipedia.org/wiki/Caf%C3%A9#Histoire_du_caf.C3.A9",
+         target("fr", "Café", Some("Histoire du caf.C3.A9"))),
+        ("https://en.wikipedia.org/wiki/Rust_(programming_language)#Version_1.21",
+         target("en", "Rust (programming language)", Some("Version 1.21"))),
+        ("https://de.wikipedia.org/wiki/AC/DC", target("de", "AC/DC", None)),
+        ("https://zh-yue.wikipedia.org/wiki/Rust", target("zh-yue", "Rust", None)),
+        ("https://en.wikipedia.org/w/index.php?title=Rust_(fungus)&oldid=1",
+         target("en", "Rust (fungus)", None)),
+        ("https://en.wikipedia.org/wiki/Special:Random", None),
+        ("https://en.wikipedia.org/wiki/", None),
+        ("https://www.wikipedia.org/", None),
+        ("https://wikipedia.org/wiki/Rust", None),
+        ("https://en.wiktionary.org/wiki/rust", None),
+    ];
+
+    for (url, expected) in cases {
+        let parsed = Url::parse(url).expect("Couldn't parse test URL");
+
+        assert_eq!(expected, recognize(&parsed), "recognizing {}", url);
+    }
+}
+
+#[test]
+fn test_decode_dots() {
+    assert_eq!("Histoire du café", decode_dots("Histoire du caf.C3.A9"));
+    assert_eq!("Rust (fungus)", decode_dots("Rust .28fungus.29"));
+    assert_eq!("HTTP/1.1 and 0.0A", decode_dots("HTTP.2F1.1 and 0.0A"));
+    assert_eq!("Version 1.0", decode_dots("Version 1.0"));
+}
@@ -0,0 +1,30 @@
+use util::http;
+
+mod client;
+mod summary;
+mod section;
+mod link;
+mod previewer;
+
+pub use self::client::Client;
+pub use self::link::{Target, recognize};
+pub use self::previewer::Wikipedia;
+
+pub const THUMBNAIL: &'static str = "https://en.wikipedia.org/static/favicon/wikipedia.ico";
+pub const GRAY: u64 = 0xEAECF0;
+
+error_chain! {
+    links {
+        Http(http::Error, http::ErrorKind);
+    }
+
+    errors {
+        UnrecognizedUrl {
+            description("URL doesn't refer to a Wikipedia article")
+        }
+        NotHtml(url: String) {
+            description("The article isn't HTML")
+            display("{} isn't HTML", url)
+        }
+    }
+}
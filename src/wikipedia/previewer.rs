@@ -0,0 +1,178 @@
+use url::Url;
+use url::percent_encoding::{utf8_percent_encode, DEFAULT_ENCODE_SET};
+
+use slog::Logger;
+
+use errors;
+use preview::{Preview, Card};
+use util::html_to_markdown::escape;
+use util::truncate::truncate;
+
+use super::{ErrorKind, Result};
+
+use super::client::Client;
+use super::link::{self, Target};
+
+/// The most characters of an extract or paragraph shown in a preview.
+pub const EXTRACT_LENGTH: usize = 1000;
+
+pub struct Wikipedia {
+    client: Client,
+}
+
+impl Wikipedia {
+    pub fn new(client: Client) -> Wikipedia {
+        Wikipedia { client }
+    }
+
+    fn card(&self) -> Card {
+        Card::new()
+            .color(super::GRAY)
+            .footer("Wikipedia", Some(super::THUMBNAIL))
+    }
+
+    pub fn preview_url(&self, url: &Url, log: &Logger) -> Result<Card> {
+        let target = link::recognize(url).ok_or(ErrorKind::UnrecognizedUrl)?;
+
+        info!(log, "Recognized Wikipedia URL"; "target" => format!("{:?}", target));
+
+        self.preview_article(&target, log)
+    }
+
+    /// Preview an article's summary, or the given section's first paragraph
+    /// if there is one.
+    pub fn preview_article(&self, target: &Target, log: &Logger) -> Result<Card> {
+        let summary = self.client.get_summary(&target.language, &target.title)?;
+
+        let mut card = self.card();
+
+        if let Some(thumbnail) = summary.thumbnail() {
+            card = card.thumbnail(thumbnail);
+        }
+
+        if let Some(ref title) = target.section {
+            // Sections are only a nicety, so any problem getting them falls
+            // back to the summary, as does a section past the size limit.
+            let sections = match self.client.get_sections(&target.language, &target.title) {
+                Ok(sections) => Some(sections),
+                Err(e) => {
+                    warn!(log, "Couldn't get article's sections"; "error" => e.to_string());
+                    None
+                }
+            };
+
+            let found = sections
+                .as_ref()
+                .and_then(|sections| sections.find(title))
+                .and_then(|section| section.first_paragraph().map(|text| (section, text)));
+
+            match found {
+                Some((section, paragraph)) => {
+                    let section_url = format!(
+                        "{}#{}",
+                        summary.url(),
+                        utf8_percent_encode(section.anchor(), DEFAULT_ENCODE_SET)
+                    );
+
+                    return Ok(card.url(section_url)
+                        .title(format!("{} § {}", summary.title(), section.title()))
+                        .description(truncate(&escape(&paragraph), EXTRACT_LENGTH, None)));
+                }
+                None => {
+                    info!(log, "Section not found, previewing the whole article";
+                          "section" => title.as_str());
+                }
+            }
+        }
+
+        let mut description = truncate(&escape(summary.extract()), EXTRACT_LENGTH, None);
+
+        if summary.is_disambiguation() {
+            description = format!("*Disambiguation page.* {}", description);
+        }
+
+        Ok(card.url(summary.url())
+            .title(summary.title())
+            .description(description))
+    }
+}
+
+impl Preview for Wikipedia {
+    fn name(&self) -> &'static str {
+        "Wikipedia"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        link::recognize(url).is_some()
+    }
+
+    fn preview(&self, url: &Url, log: &Logger) -> errors::Result<Option<Card>> {
+        self.preview_url(url, log).map(Some).map_err(From::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use util::preview_fixture::preview_fixture;
+
+    use super::super::client;
+    use super::Wikipedia;
+
+    #[test]
+    fn test_preview_article() {
+        let wikipedia = Wikipedia::new(client::fixtures());
+
+        let card = preview_fixture(&wikipedia, "https://fr.m.wikipedia.org/wiki/Caf%C3%A9");
+
+        assert_eq!(Some("Café"), card.get_title());
+        assert_eq!(Some("https://fr.wikipedia.org/wiki/Caf%C3%A9"), card.get_url());
+        assert_eq!(
+            Some(
+                "Le café est une boisson \\*énergisante\\* obtenue à partir des graines \
+                 torréfiées.",
+            ),
+            card.get_description()
+        );
+        assert!(card.get_thumbnail().unwrap().ends_with("/320px-Tasse.jpg"));
+    }
+
+    #[test]
+    fn test_preview_section() {
+        let wikipedia = Wikipedia::new(client::fixtures());
+
+        let url = "https://fr.wikipedia.org/wiki/Caf%C3%A9#Histoire_du_caf%C3%A9";
+        let card = preview_fixture(&wikipedia, url);
+
+        assert_eq!(Some("Café § Histoire du café"), card.get_title());
+        assert_eq!(
+            Some("https://fr.wikipedia.org/wiki/Caf%C3%A9#Histoire_du_caf%C3%A9"),
+            card.get_url()
+        );
+        assert_eq!(
+            Some("Le café est d'abord consommé au Yémen."),
+            card.get_description()
+        );
+
+        // Older links encode sections the way MediaWiki used to.
+        let url = "https://fr.wikipedia.org/wiki/Caf%C3%A9#Histoire_du_caf.C3.A9";
+        let dotted = preview_fixture(&wikipedia, url);
+
+        assert_eq!(card, dotted);
+
+        // Subsections are nested within their sections.
+        let url = "https://fr.wikipedia.org/wiki/Caf%C3%A9#En_Europe";
+        let card = preview_fixture(&wikipedia, url);
+
+        assert_eq!(Some("Café § En Europe"), card.get_title());
+        assert_eq!(
+            Some("Le café arrive en Europe au XVIIe siècle."),
+            card.get_description()
+        );
+
+        // Unknown sections fall back to the summary.
+        let url = "https://fr.wikipedia.org/wiki/Caf%C3%A9#Nulle_part";
+        let card = preview_fixture(&wikipedia, url);
+
+        assert_eq!(Some("Café"), card.get_title());
+    }
+}
@@ -0,0 +1,182 @@
+use html5ever::parse_document;
+use html5ever::rcdom::{NodeData, RcDom, Handle};
+use html5ever::tendril::TendrilSink;
+
+use util::dom::{attribute, has_class};
+
+use super::link::decode_dots;
+
+const HEADINGS: &'static [&'static str] = &["h1", "h2", "h3", "h4", "h5", "h6"];
+
+/// The sections of an article, from the HTML the REST API serves for it,
+/// where each section is wrapped in its own `<section>` element.
+#[derive(Clone, Debug)]
+pub struct Sections {
+    sections: Vec<Section>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Section {
+    /// The title of the section as plain text.
+    title: String,
+
+    /// The ID of the section's heading, e.g. `Histoire_du_café`.
+    anchor: String,
+
+    /// The text of the section's first paragraph, not counting any in its
+    /// subsections.
+    first_paragraph: Option<String>,
+}
+
+impl Sections {
+    /// Find the sections in an article's HTML, which is parsed leniently
+    /// since it may have been cut off at the size limit.
+    pub fn parse(html: &str) -> Sections {
+        let dom = parse_document(RcDom::default(), Default::default()).one(html);
+
+        let mut sections = vec![];
+        collect(&dom.document, &mut sections);
+
+        Sections { sections }
+    }
+
+    /// Find a section by its title as it appears in a link, with spaces
+    /// rather than underscores.
+    ///
+    /// Older links escape sections with dots rather than percent signs, but
+    /// dots are common in titles by themselves, e.g. `Version 1.21`, so the
+    /// title is matched both as-is and with any such escapes decoded.
+    pub fn find(&self, title: &str) -> Option<&Section> {
+        let decoded = decode_dots(title);
+
+        self.sections.iter().find(|section| {
+            let anchor = section.anchor.replace('_', " ");
+
+            anchor == title || anchor == decoded
+        })
+    }
+}
+
+impl Section {
+    pub fn anchor(&self) -> &str {
+        &self.anchor
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn first_paragraph(&self) -> Option<&str> {
+        self.first_paragraph.as_ref().map(String::as_str)
+    }
+}
+
+fn is_element(node: &Handle, names: &[&str]) -> bool {
+    match node.data {
+        NodeData::Element { ref name, .. } => names.iter().any(|n| &name.local == *n),
+        _ => false,
+    }
+}
+
+/// Collect every section with a heading, including subsections, in
+/// document order.
+fn collect(node: &Handle, sections: &mut Vec<Section>) {
+    if is_element(node, &["section"]) {
+        let heading = node.children
+            .borrow()
+            .iter()
+            .find(|child| is_element(child, HEADINGS))
+            .cloned();
+
+        let anchor = heading.as_ref().and_then(|heading| attribute(heading, "id"));
+
+        if let (Some(heading), Some(anchor)) = (heading, anchor) {
+            let mut paragraphs = vec![];
+            find_paragraphs(node, &mut paragraphs);
+
+            sections.push(Section {
+                title: collapse(&readable_text(&heading)),
+                anchor,
+                first_paragraph: paragraphs
+                    .iter()
+                    .map(|paragraph| collapse(&readable_text(paragraph)))
+                    .find(|text| !text.is_empty()),
+            });
+        }
+    }
+
+    for child in node.children.borrow().iter() {
+        collect(child, sections);
+    }
+}
+
+/// Collapse runs of whitespace into single spaces, trimming the ends.
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The readable text of a node, without citation markers such as `[1]`.
+fn readable_text(node: &Handle) -> String {
+    let mut text = String::new();
+
+    match node.data {
+        NodeData::Text { ref contents } => text.push_str(&contents.borrow()),
+        NodeData::Element { .. } if has_class(node, "reference") ||
+                                    is_element(node, &["style", "script"]) => {
+            return text;
+        }
+        _ => {}
+    }
+
+    for child in node.children.borrow().iter() {
+        text.push_str(&readable_text(child));
+    }
+
+    text
+}
+
+/// Collect the paragraphs within a section in document order, leaving out
+/// those of its subsections.
+fn find_paragraphs(node: &Handle, found: &mut Vec<Handle>) {
+    for child in node.children.borrow().iter() {
+        if is_element(child, &["p"]) {
+            found.push(child.clone());
+        } else if !is_element(child, &["section"]) {
+            find_paragraphs(child, found);
+        }
+    }
+}
+
+#[test]
+fn test_parse() {
+    let html = r#"<html><body>
+        <section data-mw-section-id="0"><p>The lead.</p></section>
+        <section data-mw-section-id="1">
+          <h2 id="Early_history">Early <i>history</i></h2>
+          <p></p>
+          <div class="thumb"><p>Coffee was <b>first</b> drunk in
+            Yemen.<sup class="mw-ref reference"><a href="#cite_note-1">[1]</a></sup></p></div>
+          <section data-mw-section-id="2">
+            <h3 id="Version_1.21">Version 1.21</h3>
+            <ul><li>Only a list.</li></ul>
+          </section>
+          <p>Then elsewhere.</p>
+        </section>
+      </body></html>"#;
+
+    let sections = Sections::parse(html);
+
+    let early = sections.find("Early history").expect("Couldn't find section");
+
+    assert_eq!("Early history", early.title());
+    assert_eq!("Early_history", early.anchor());
+    assert_eq!(Some("Coffee was first drunk in Yemen."), early.first_paragraph());
+
+    // Dots that look like escapes are only decoded if that's what matches.
+    let subsection = sections.find("Version 1.21").expect("Couldn't find subsection");
+
+    assert_eq!("Version_1.21", subsection.anchor());
+    assert_eq!(None, subsection.first_paragraph());
+
+    assert!(sections.find("The lead").is_none());
+}
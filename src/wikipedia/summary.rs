@@ -0,0 +1,56 @@
+/// The summary of an article, as served by the REST API.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Summary {
+    title: String,
+
+    /// The lead of the article as plain text.
+    #[serde(default)]
+    extract: String,
+
+    /// e.g. `standard` or `disambiguation`.
+    #[serde(rename = "type", default)]
+    kind: String,
+
+    #[serde(default)]
+    thumbnail: Option<Thumbnail>,
+
+    content_urls: ContentUrls,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Thumbnail {
+    source: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct ContentUrls {
+    desktop: Urls,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Urls {
+    page: String,
+}
+
+impl Summary {
+    pub fn title(&self) -> String {
+        self.title.replace('_', " ")
+    }
+
+    pub fn extract(&self) -> &str {
+        &self.extract
+    }
+
+    pub fn is_disambiguation(&self) -> bool {
+        self.kind == "disambiguation"
+    }
+
+    pub fn thumbnail(&self) -> Option<&str> {
+        self.thumbnail.as_ref().map(|thumbnail| thumbnail.source.as_str())
+    }
+
+    /// The URL of the article on the desktop site.
+    pub fn url(&self) -> &str {
+        &self.content_urls.desktop.page
+    }
+}